@@ -0,0 +1,59 @@
+//! A minimal wasmtime host for the `mina-signer` component,
+//! build the component first with
+//! `cargo build --release --target wasm32-wasip2 --features component`
+//! at the repository root
+
+use std::path::{Path, PathBuf};
+use wasmtime::component::{Component, Linker, ResourceTable};
+use wasmtime::{Config, Engine, Store};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView};
+
+wasmtime::component::bindgen!({
+    world: "mina-signer",
+    path: "../../wit",
+});
+
+pub use exports::chainsafe::mina_signer::signer::*;
+
+pub struct HostState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl WasiView for HostState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+/// Path of the component built by `cargo build --release --target wasm32-wasip2 --features component`,
+/// can be overridden with `MINA_SIGNER_COMPONENT`
+pub fn default_component_path() -> PathBuf {
+    match std::env::var_os("MINA_SIGNER_COMPONENT") {
+        Some(path) => path.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../target/wasm32-wasip2/release/mina_signer_wasm.wasm"),
+    }
+}
+
+pub fn instantiate(path: impl AsRef<Path>) -> anyhow::Result<(Store<HostState>, MinaSigner)> {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let component = Component::from_file(&engine, path)?;
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+    let mut store = Store::new(
+        &engine,
+        HostState {
+            ctx: WasiCtxBuilder::new().build(),
+            table: ResourceTable::new(),
+        },
+    );
+    let (bindings, _) = MinaSigner::instantiate(&mut store, &component, &linker)?;
+    Ok((store, bindings))
+}
@@ -0,0 +1,84 @@
+use mina_signer_component_host::*;
+
+#[test]
+fn sign_and_verify_payment() -> anyhow::Result<()> {
+    let (mut store, bindings) = instantiate(default_component_path())?;
+    let signer = bindings.chainsafe_mina_signer_signer();
+    let from = signer.call_gen_keys(&mut store)?;
+    let to = signer.call_gen_keys(&mut store)?;
+    assert!(signer.call_verify_keypair(&mut store, &from)?.unwrap());
+    assert_eq!(
+        signer
+            .call_derive_public_key(&mut store, &from.private_key)?
+            .unwrap(),
+        from.public_key
+    );
+
+    let payment = Payment {
+        to: to.public_key,
+        from: from.public_key,
+        fee: 1,
+        amount: 2,
+        nonce: 3,
+        memo: Some("memo".into()),
+        valid_until: Some(u32::MAX),
+    };
+    let signed_payment = signer
+        .call_sign_payment(&mut store, Network::Mainnet, &payment, &from.private_key)?
+        .unwrap();
+    assert!(signer
+        .call_verify_payment(&mut store, Network::Mainnet, &signed_payment)?
+        .unwrap());
+    assert!(!signer
+        .call_verify_payment(&mut store, Network::Testnet, &signed_payment)?
+        .unwrap());
+    assert!(signer
+        .call_hash_payment(&mut store, &signed_payment)?
+        .unwrap()
+        .starts_with("Ckp"));
+    Ok(())
+}
+
+#[test]
+fn sign_and_verify_message_and_stake_delegation() -> anyhow::Result<()> {
+    let (mut store, bindings) = instantiate(default_component_path())?;
+    let signer = bindings.chainsafe_mina_signer_signer();
+    let from = signer.call_gen_keys(&mut store)?;
+    let to = signer.call_gen_keys(&mut store)?;
+
+    let signed_message = signer
+        .call_sign_message(
+            &mut store,
+            Network::Testnet,
+            "This is a sample message.",
+            &from,
+        )?
+        .unwrap();
+    assert!(signer
+        .call_verify_message(&mut store, Network::Testnet, &signed_message)?
+        .unwrap());
+
+    let stake_delegation = StakeDelegation {
+        to: to.public_key,
+        from: from.public_key,
+        fee: 1,
+        nonce: 3,
+        memo: None,
+        valid_until: None,
+    };
+    let signed_stake_delegation = signer
+        .call_sign_stake_delegation(
+            &mut store,
+            Network::Testnet,
+            &stake_delegation,
+            &from.private_key,
+        )?
+        .unwrap();
+    assert!(signer
+        .call_verify_stake_delegation(&mut store, Network::Testnet, &signed_stake_delegation)?
+        .unwrap());
+    assert!(signer
+        .call_hash_stake_delegation(&mut store, &signed_stake_delegation)?
+        .is_ok());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+import path from "node:path";
+import ClientJs from "mina-signer";
+import { AsyncClient } from "../../package/async/node";
+
+const clientJs = new ClientJs({ network: "mainnet" });
+const asyncClient = new AsyncClient({
+  network: "mainnet",
+  workers: 2,
+  modulePath: path.resolve(__dirname, "pkg/mina_signer_wasm.js"),
+});
+
+afterAll(() => asyncClient.terminate());
+
+test("genKeys and verifyKeypair", async () => {
+  const keypair = await asyncClient.genKeys();
+  expect(await asyncClient.verifyKeypair(keypair)).toBe(true);
+  expect(clientJs.verifyKeypair(keypair)).toBe(true);
+});
+
+test("signPayment and verifyPayment", async () => {
+  const fromKeypair = clientJs.genKeys();
+  const payment = {
+    to: clientJs.genKeys().publicKey,
+    from: fromKeypair.publicKey,
+    fee: 1n,
+    amount: "1",
+    nonce: 3,
+    memo: "memo",
+  };
+  const signedPayment = await asyncClient.signPayment(
+    payment,
+    fromKeypair.privateKey
+  );
+  expect(clientJs.verifyPayment(signedPayment)).toBe(true);
+  expect(await asyncClient.verifyPayment(signedPayment)).toBe(true);
+  expect(await asyncClient.hashPayment(signedPayment)).toBe(
+    clientJs.hashPayment(signedPayment)
+  );
+});
+
+test("batch keeps input order", async () => {
+  const fromKeypair = clientJs.genKeys();
+  const payments = Array.from({ length: 8 }, (_, nonce) => ({
+    to: fromKeypair.publicKey,
+    from: fromKeypair.publicKey,
+    fee: 1,
+    amount: 1,
+    nonce,
+  }));
+  const signedPayments = await asyncClient.batch(
+    "signPayment",
+    payments.map((payment) => [payment, fromKeypair.privateKey])
+  );
+  expect(signedPayments.map((signed) => signed.data.nonce)).toEqual(
+    payments.map((payment) => payment.nonce)
+  );
+  const verified = await asyncClient.batch(
+    "verifyPayment",
+    signedPayments.map((signed) => [signed])
+  );
+  expect(verified).toEqual(payments.map(() => true));
+});
+
+test("errors are rejected with the wasm error message", async () => {
+  await expect(asyncClient.derivePublicKey("invalid")).rejects.toThrow();
+});
+
+test("workers that fail to start are dropped", async () => {
+  const broken = new AsyncClient({
+    network: "mainnet",
+    workers: 2,
+    modulePath: path.resolve(__dirname, "pkg/mina_signer_wasm.js"),
+    policy: "{",
+  });
+  await expect(broken.genKeys()).rejects.toThrow("Invalid signing policy");
+  // both workers exit, later requests are rejected instead of waiting forever
+  await new Promise((resolve) => setTimeout(resolve, 1000));
+  await expect(broken.genKeys()).rejects.toThrow(
+    "AsyncClient has no live workers: Invalid signing policy"
+  );
+  await broken.terminate();
+  await expect(broken.genKeys()).rejects.toThrow(
+    "AsyncClient has been terminated"
+  );
+});
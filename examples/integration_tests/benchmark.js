@@ -1,9 +1,15 @@
 const ClientJs = require("mina-signer");
 const wasm = require("./pkg/mina_signer_wasm");
 const ClientWasm = wasm.Client;
+const fs = require("fs");
+// Built by `yarn benchmark:baseline`, to compare with the getter based js conversions
+const ClientBaseline = fs.existsSync(`${__dirname}/pkg-baseline`)
+	? require("./pkg-baseline/mina_signer_wasm").Client
+	: undefined;
 
 const clientJs = new ClientJs({ network: "mainnet" });
 const clientWasm = new ClientWasm({ network: "mainnet" });
+const clientBaseline = ClientBaseline && new ClientBaseline({ network: "mainnet" });
 
 const Benchmark = require("benchmark");
 
@@ -26,6 +32,16 @@ function main() {
 		validUntil: 0xFFFFFFFF,
 	};
 	const signedPayment = clientJs.signPayment(payment, fromKeypair.privateKey);
+	// u64 / u32 fields passed as string, to measure the js interop overhead
+	const signedPaymentString = {
+		...signedPayment,
+		data: { ...signedPayment.data, fee: "1", amount: "2", nonce: "3", validUntil: "4294967295" },
+	};
+	// u64 / u32 fields passed as bigint, to measure the js interop overhead
+	const signedPaymentBigint = {
+		...signedPayment,
+		data: { ...signedPayment.data, fee: 1n, amount: 2n, nonce: 3n, validUntil: 4294967295n },
+	};
 	const stakeDelegation = {
 		to: toKeypair.publicKey,
 		from: fromKeypair.publicKey,
@@ -74,7 +90,7 @@ function main() {
 		mint_tokens: null,
 	},);
 
-	new Benchmark.Suite()
+	const suite = new Benchmark.Suite()
 		.on(
 			"cycle",
 			function (event) {
@@ -213,6 +229,18 @@ function main() {
 				clientWasm.hashPayment(signedPayment);
 			},
 		)
+		.add(
+			"[wasm] hashPayment - string fields",
+			function () {
+				clientWasm.hashPayment(signedPaymentString);
+			},
+		)
+		.add(
+			"[wasm] hashPayment - bigint fields",
+			function () {
+				clientWasm.hashPayment(signedPaymentBigint);
+			},
+		)
 		.add(
 			"[js]   hashStakeDelegation",
 			function () {
@@ -256,8 +284,26 @@ function main() {
 					signedRosettaTransactionStakeDelegation,
 				);
 			},
-		)
-		.run();
+		);
+	if (clientBaseline) {
+		const baseline = {
+			signMessage: () => clientBaseline.signMessage(message, keypair),
+			verifyMessage: () => clientBaseline.verifyMessage(signedMessage),
+			signPayment: () => clientBaseline.signPayment(payment, fromKeypair.privateKey),
+			verifyPayment: () => clientBaseline.verifyPayment(signedPayment),
+			signStakeDelegation: () =>
+				clientBaseline.signStakeDelegation(stakeDelegation, fromKeypair.privateKey),
+			verifyStakeDelegation: () =>
+				clientBaseline.verifyStakeDelegation(signedStakeDelegation),
+			hashPayment: () => clientBaseline.hashPayment(signedPayment),
+			hashStakeDelegation: () =>
+				clientBaseline.hashStakeDelegation(signedStakeDelegation),
+		};
+		for (const [name, fn] of Object.entries(baseline)) {
+			suite.add(`[base] ${name}`, fn);
+		}
+	}
+	suite.run();
 }
 
 main();
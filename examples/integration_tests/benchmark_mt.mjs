@@ -0,0 +1,73 @@
+import assert from "node:assert";
+import { createRequire } from "node:module";
+import os from "node:os";
+import Benchmark from "benchmark";
+import { Client as ClientWasmMt } from "./pkg-mt/mina_signer_wasm.js";
+import { initParallel } from "./thread_pool.mjs";
+
+const require = createRequire(import.meta.url);
+const ClientWasm = require("./pkg/mina_signer_wasm").Client;
+
+const BATCH_SIZE = 100;
+
+async function main() {
+	await initParallel(os.cpus().length);
+
+	const clientWasm = new ClientWasm({ network: "mainnet" });
+	const clientWasmMt = new ClientWasmMt({ network: "mainnet" });
+
+	const keypair = clientWasm.genKeys();
+	const payments = Array.from({ length: BATCH_SIZE }, (_, nonce) => ({
+		to: keypair.publicKey,
+		from: keypair.publicKey,
+		fee: 1,
+		amount: 2,
+		nonce,
+		memo: "memo",
+	}));
+	const signedPayments = clientWasmMt.signPayments(payments, keypair.privateKey);
+	assert.deepStrictEqual(
+		signedPayments,
+		payments.map((payment) => clientWasm.signPayment(payment, keypair.privateKey)),
+	);
+	assert.ok(clientWasmMt.verifyPayments(signedPayments).every((verified) => verified));
+
+	new Benchmark.Suite()
+		.on(
+			"cycle",
+			function (event) {
+				console.log("\x1b[35m%s\x1b[0m", String(event.target));
+			},
+		)
+		.add(
+			`[wasm]    signPayment x ${BATCH_SIZE}`,
+			function () {
+				for (const payment of payments) {
+					clientWasm.signPayment(payment, keypair.privateKey);
+				}
+			},
+		)
+		.add(
+			`[wasm-mt] signPayments(${BATCH_SIZE})`,
+			function () {
+				clientWasmMt.signPayments(payments, keypair.privateKey);
+			},
+		)
+		.add(
+			`[wasm]    verifyPayment x ${BATCH_SIZE}`,
+			function () {
+				for (const signedPayment of signedPayments) {
+					clientWasm.verifyPayment(signedPayment);
+				}
+			},
+		)
+		.add(
+			`[wasm-mt] verifyPayments(${BATCH_SIZE})`,
+			function () {
+				clientWasmMt.verifyPayments(signedPayments);
+			},
+		)
+		.run();
+}
+
+main();
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Builds BASELINE_REF to pkg-baseline for `yarn benchmark:baseline`, it defaults to the
+# last commit that read js objects field by field through getters
+set -e
+ref="${BASELINE_REF:-2199efb^}"
+dir="$(git rev-parse --show-toplevel)/target/baseline"
+git worktree remove --force "$dir" 2>/dev/null || true
+git worktree add --detach "$dir" "$ref"
+wasm-pack build -t nodejs -d "$PWD/pkg-baseline" "$dir"
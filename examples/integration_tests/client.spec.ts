@@ -1,5 +1,28 @@
 import ClientJs from "mina-signer";
-import { Client as ClientWasm } from "./pkg/mina_signer_wasm";
+import {
+  Client as ClientWasm,
+  Keyring,
+  toNanomina,
+  fromNanomina,
+  formatMina,
+  addNanomina,
+  subNanomina,
+  signatureToBase58,
+  signatureFromBase58,
+  signatureToRosettaHex,
+  signatureFromRosettaHex,
+  signatureToBytes,
+  signatureFromBytes,
+  isValidPublicKey,
+  isValidPrivateKey,
+  encodeBase58Check,
+  decodeBase58Check,
+  identifyBase58Check,
+  emptyReceiptChainHash,
+  timeToGlobalSlot,
+  globalSlotToTime,
+  timedBalanceAt,
+} from "./pkg/mina_signer_wasm";
 
 const clientJs = new ClientJs({ network: "mainnet" });
 const clientWasm = new ClientWasm({ network: "mainnet" });
@@ -23,6 +46,50 @@ test("derivePublicKey", () => {
   expect(derivedPublicKeyJs).toBe(derivedPublicKeyWasm);
 });
 
+test("isValidPublicKey and isValidPrivateKey", () => {
+  const { publicKey, privateKey } = clientJs.genKeys();
+  expect(isValidPublicKey(publicKey)).toBe(true);
+  expect(isValidPrivateKey(privateKey)).toBe(true);
+  expect(isValidPublicKey(privateKey)).toBe(false);
+  expect(isValidPrivateKey(publicKey)).toBe(false);
+  for (const input of ["", "B62q", publicKey.slice(0, -1), publicKey + "1", 42, null, undefined]) {
+    expect(isValidPublicKey(input)).toBe(false);
+    expect(isValidPrivateKey(input)).toBe(false);
+  }
+  expect(() => clientWasm.derivePublicKey("EKE")).toThrow("Invalid private key");
+});
+
+test("base58check codec", () => {
+  const { publicKey, privateKey } = clientWasm.genKeys();
+  expect(identifyBase58Check(publicKey)).toBe("publicKey");
+  expect(identifyBase58Check(privateKey)).toBe("privateKey");
+  expect(
+    identifyBase58Check("3NKjZ5fjms6BMaH4aq7DopPGyMY7PbG6vhRsX5XnYRxih8i9G7dj")
+  ).toBe("stateHash");
+  expect(identifyBase58Check("not base58check")).toBeUndefined();
+  expect(identifyBase58Check(42)).toBeUndefined();
+
+  const decoded = decodeBase58Check(publicKey);
+  expect(decoded.kind).toBe("publicKey");
+  expect(decoded.versionByte).toBe(0xcb);
+  expect(decoded.bytes).toBeInstanceOf(Uint8Array);
+  expect(decoded.bytes.length).toBe(35);
+  expect(encodeBase58Check("publicKey", decoded.bytes)).toBe(publicKey);
+  expect(() => decodeBase58Check(publicKey, "ledgerHash")).toThrow(
+    "Invalid base58check"
+  );
+
+  const hash = clientWasm.hashPayment(
+    clientWasm.signPayment(
+      { to: publicKey, from: publicKey, fee: 1, amount: 1, nonce: 0 },
+      privateKey
+    )
+  );
+  expect(identifyBase58Check(hash)).toBe("transactionHash");
+  const memo = encodeBase58Check("memo", new Uint8Array(34));
+  expect(decodeBase58Check(memo, "memo").bytes).toEqual(new Uint8Array(34));
+});
+
 test("publicKeyToRaw", () => {
   const pubkey = clientJs.genKeys().publicKey;
   const rawPubkeyJs = clientJs.publicKeyToRaw(pubkey);
@@ -30,6 +97,33 @@ test("publicKeyToRaw", () => {
   expect(rawPubkeyJs).toBe(rawPubkeyWasm);
 });
 
+test("public key point and compressed forms", () => {
+  const { publicKey } = clientWasm.genKeys();
+  const point = clientWasm.publicKeyToPoint(publicKey);
+  expect(point.x).toMatch(/^[0-9]+$/);
+  expect(clientWasm.publicKeyToRaw(publicKey)).toBe(point.x + point.y);
+  expect(clientWasm.publicKeyFromPoint(point)).toBe(publicKey);
+
+  const hexPoint = clientWasm.publicKeyToPoint(publicKey, "hex");
+  expect(hexPoint.x).toMatch(/^0x[0-9a-f]{64}$/);
+  expect(BigInt(hexPoint.y)).toBe(BigInt(point.y));
+  expect(clientWasm.publicKeyFromPoint(hexPoint)).toBe(publicKey);
+
+  const compressed = clientWasm.publicKeyToCompressed(publicKey);
+  expect(compressed).toEqual({
+    x: point.x,
+    isOdd: BigInt(point.y) % 2n === 1n,
+  });
+  expect(clientWasm.publicKeyFromCompressed(compressed)).toBe(publicKey);
+
+  expect(() =>
+    clientWasm.publicKeyFromPoint({ ...point, y: (BigInt(point.y) + 1n).toString() })
+  ).toThrow("not on the curve");
+  expect(() => clientWasm.publicKeyToPoint(publicKey, "binary" as any)).toThrow(
+    "Invalid format"
+  );
+});
+
 test("signMessage and verifyMessage", () => {
   const message = "This is a sample message.";
   const keypair = clientWasm.genKeys();
@@ -43,6 +137,37 @@ test("signMessage and verifyMessage", () => {
   expect(clientWasm.verifyMessage(signedMessageWasm)).toBe(true);
 });
 
+test("verifyMessage strict mode and Auro shape", () => {
+  const keypair = clientWasm.genKeys();
+  const signed = clientWasm.signMessage("hello", keypair);
+  const tampered = {
+    ...signed,
+    signature: { ...signed.signature, string: "goodbye" },
+  };
+  expect(clientWasm.verifyMessage(tampered)).toBe(true);
+  expect(clientWasm.verifyMessage(tampered, true)).toBe(false);
+  expect(clientWasm.verifyMessage(signed, true)).toBe(true);
+  expect(
+    clientWasm.verifyMessage(
+      {
+        ...signed,
+        signature: { ...signed.signature, signer: clientWasm.genKeys().publicKey },
+      },
+      true
+    )
+  ).toBe(false);
+
+  const auro = {
+    publicKey: keypair.publicKey,
+    data: "hello",
+    signature: signed.signature.signature,
+  };
+  expect(clientWasm.verifyMessage(auro)).toBe(true);
+  expect(clientWasm.verifyMessage(auro, true)).toBe(true);
+  expect(clientWasm.verifyMessage({ ...auro, data: "bye" })).toBe(false);
+  expect(clientWasm.verifyMessageDetailed(auro)).toEqual({ valid: true });
+});
+
 test("signPayment and verifyPayment", () => {
   const fromKeypair = clientWasm.genKeys();
   const toKeypair = clientWasm.genKeys();
@@ -95,6 +220,216 @@ test("signStakeDelegation and verifyStakeDelegation", () => {
   );
 });
 
+test("signPayment accepts number, bigint and string fields", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    memo: "memo",
+  };
+  const signed = [
+    { fee: 1, amount: 2, nonce: 3, validUntil: 4 },
+    { fee: 1n, amount: 2n, nonce: 3n, validUntil: 4n },
+    { fee: "1", amount: "2", nonce: "3", validUntil: "4" },
+  ].map((fields) =>
+    clientWasm.signPayment({ ...payment, ...fields }, keypair.privateKey)
+  );
+  for (const signedPayment of signed) {
+    expect(signedPayment.signature).toEqual(signed[0].signature);
+    expect(clientWasm.verifyPayment(signedPayment)).toBe(true);
+  }
+});
+
+test("signPayment rejects unsafe numbers with field specific errors", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: keypair.publicKey,
+    from: keypair.publicKey,
+    fee: 1,
+    amount: 2,
+    nonce: 3,
+  };
+  const sign = (fields: object) =>
+    clientWasm.signPayment({ ...payment, ...fields }, keypair.privateKey);
+  expect(() => sign({ amount: 2 ** 53 })).toThrow(
+    "Invalid amount: 9.007199254740992e15 is above Number.MAX_SAFE_INTEGER"
+  );
+  expect(() => sign({ fee: 1e21 })).toThrow("Invalid fee:");
+  expect(() => sign({ fee: 1.5 })).toThrow("Invalid fee: 1.5 is not an integer");
+  expect(() => sign({ nonce: -1 })).toThrow("Invalid nonce: -1 is negative");
+  expect(() => sign({ amount: "1e3" })).toThrow("exponent notation");
+  expect(() => sign({ amount: "18446744073709551616" })).toThrow(
+    "Invalid amount: '18446744073709551616' exceeds the u64 range"
+  );
+  expect(() => sign({ nonce: 2 ** 32 })).toThrow("exceeds the u32 range");
+  // Large values still work as bigint or decimal string
+  expect(sign({ amount: 2n ** 53n }).signature).toEqual(
+    sign({ amount: "9007199254740992" }).signature
+  );
+});
+
+test("global slot conversions and validUntil expiresAt", () => {
+  const genesis = new Date("2021-03-17T00:00:00Z");
+  expect(timeToGlobalSlot(genesis, "mainnet")).toBe(0);
+  expect(timeToGlobalSlot(genesis.getTime() + 179_999, "mainnet")).toBe(0);
+  expect(timeToGlobalSlot(genesis.getTime() + 180_000, "mainnet")).toBe(1);
+  expect(globalSlotToTime(1, "mainnet").toISOString()).toBe(
+    "2021-03-17T00:03:00.000Z"
+  );
+  expect(clientWasm.globalSlotAt(new Date("2021-03-18T00:00:00Z"))).toBe(480);
+  expect(clientWasm.globalSlotStart(480).getTime()).toBe(
+    genesis.getTime() + 480 * 180_000
+  );
+  expect(() => timeToGlobalSlot(genesis.getTime() - 1, "mainnet")).toThrow(
+    "before the genesis"
+  );
+
+  const custom = { genesisTimestamp: 1_000, slotDuration: 10 };
+  expect(timeToGlobalSlot(1_025, custom)).toBe(2);
+  expect(globalSlotToTime(2, custom).getTime()).toBe(1_020);
+  const customClient = new ClientWasm({ network: "testnet", slotTime: custom });
+  expect(customClient.globalSlotAt(1_025)).toBe(2);
+
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: 10_000_000,
+    amount: 1_000_000_000,
+    nonce: 0,
+  };
+  // Blocks of slot 1000 may be produced after its start, slot 999 is the last one that ends by then
+  const expiresAt = globalSlotToTime(1000, "mainnet");
+  const signed = clientWasm.signPayment(
+    { ...payment, validUntil: { expiresAt } },
+    keypair.privateKey
+  );
+  expect(signed.data.validUntil).toBe(999);
+  expect(clientWasm.verifyPayment(signed)).toBe(true);
+  expect(
+    clientWasm.signPayment({ ...payment, validUntil: 999 }, keypair.privateKey)
+      .signature
+  ).toEqual(signed.signature);
+  expect(() =>
+    clientWasm.signPayment(
+      { ...payment, validUntil: { expiresAt: genesis } },
+      keypair.privateKey
+    )
+  ).toThrow("Invalid validUntil.expiresAt");
+
+  // every entry point taking a transaction resolves expiresAt the same way
+  const unresolved = { ...payment, validUntil: { expiresAt } };
+  expect(clientWasm.describeTransaction(unresolved).validUntil).toBe(999);
+  expect(
+    clientWasm.describeTransaction({ ...signed, data: unresolved })
+  ).toEqual(clientWasm.describeTransaction(signed));
+  const receipt = emptyReceiptChainHash();
+  expect(clientWasm.receiptChainHash(receipt, unresolved)).toBe(
+    clientWasm.receiptChainHash(receipt, signed)
+  );
+  expect(
+    clientWasm.signedCommandToRosettaTransaction({ ...signed, data: unresolved })
+  ).toBe(clientWasm.signedCommandToRosettaTransaction(signed));
+  expect(
+    clientWasm.signedCommandToRosettaTransaction(
+      JSON.stringify(
+        {
+          ...signed,
+          data: { ...unresolved, validUntil: { expiresAt: expiresAt.getTime() } },
+        },
+        (_, v) => (typeof v === "bigint" ? v.toString() : v)
+      )
+    )
+  ).toBe(clientWasm.signedCommandToRosettaTransaction(signed));
+  const ledger = { accounts: [{ pk: keypair.publicKey, balance: "10" }] };
+  expect(
+    clientWasm.simulateLedger(ledger, [{ ...signed, data: unresolved }], 999)
+      .transactions[0]
+  ).toMatchObject({ applied: true });
+});
+
+test("signPaymentBatch", () => {
+  const sender = clientWasm.genKeys();
+  const [alice, bob] = [clientWasm.genKeys(), clientWasm.genKeys()];
+  const csv = `to,amount,fee,memo
+${alice.publicKey},1.5 MINA,,
+${bob.publicKey}, 2000000000 ,0.02 MINA,"thanks, ""bob"""
+`;
+  const { payments, hashes, manifest } = clientWasm.signPaymentBatch(
+    { nonce: 7, fee: 10_000_000, memo: "airdrop", recipients: csv },
+    sender.privateKey
+  );
+  expect(payments.map((p) => p.data.nonce)).toEqual([7, 8]);
+  expect(payments[0].data).toMatchObject({
+    to: alice.publicKey,
+    from: sender.publicKey,
+    memo: "airdrop",
+  });
+  expect(payments[1].data.memo).toBe('thanks, "bob"');
+  expect(BigInt(payments[1].data.fee)).toBe(20_000_000n);
+  payments.forEach((payment, i) => {
+    expect(clientWasm.verifyPayment(payment)).toBe(true);
+    expect(hashes?.[i]).toBe(clientWasm.hashPayment(payment));
+  });
+  expect(manifest).toEqual({
+    from: sender.publicKey,
+    count: 2,
+    firstNonce: 7,
+    nextNonce: 9,
+    totalAmount: 3_500_000_000n,
+    totalFees: 30_000_000n,
+    total: 3_530_000_000n,
+  });
+
+  const fromList = clientWasm.signPaymentBatch(
+    {
+      nonce: 7,
+      fee: 10_000_000,
+      memo: "airdrop",
+      recipients: [
+        { to: alice.publicKey, amount: "1.5 MINA" },
+        {
+          to: bob.publicKey,
+          amount: 2_000_000_000n,
+          fee: 20_000_000,
+          memo: 'thanks, "bob"',
+        },
+      ],
+    },
+    sender.privateKey
+  );
+  expect(fromList.payments.map((p) => p.signature)).toEqual(
+    payments.map((p) => p.signature)
+  );
+
+  expect(() =>
+    clientWasm.signPaymentBatch(
+      { nonce: 0, fee: 10_000_000, recipients: `${alice.publicKey},-1` },
+      sender.privateKey
+    )
+  ).toThrow("line 1: amount '-1' is negative");
+  expect(() =>
+    clientWasm.signPaymentBatch(
+      {
+        nonce: 0,
+        fee: 10_000_000,
+        recipients: [{ to: alice.publicKey, amount: 1.5 }],
+      },
+      sender.privateKey
+    )
+  ).toThrow("Invalid recipients[0].amount");
+  expect(() =>
+    clientWasm.signPaymentBatch(
+      {
+        nonce: 2 ** 32 - 1,
+        fee: 10_000_000,
+        recipients: [{ to: alice.publicKey, amount: 1 }],
+      },
+      sender.privateKey
+    )
+  ).toThrow("overflow the nonce");
+});
+
 test("hashPayment", () => {
   // From block mainnet-117896-3NKjZ5fjms6BMaH4aq7DopPGyMY7PbG6vhRsX5XnYRxih8i9G7dj
   const payment = {
@@ -132,6 +467,44 @@ test("hashStakeDelegation", () => {
   expect(hashJs).toBe(hashWasm);
 });
 
+test("receipt chain hash", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: 10_000_000,
+    amount: 1_000_000_000,
+    nonce: 0,
+  };
+  const stakeDelegation = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: 10_000_000,
+    nonce: 1,
+  };
+  const empty = emptyReceiptChainHash();
+  expect(identifyBase58Check(empty)).toBe("receiptChainHash");
+
+  const signedPayment = clientWasm.signPayment(payment, keypair.privateKey);
+  const first = clientWasm.receiptChainHash(empty, signedPayment);
+  expect(identifyBase58Check(first)).toBe("receiptChainHash");
+  // The signature is not part of the hash
+  expect(clientWasm.receiptChainHash(empty, payment)).toBe(first);
+  const second = clientWasm.receiptChainHash(first, stakeDelegation);
+  expect(second).not.toBe(first);
+
+  expect(
+    clientWasm.verifyReceiptChain(empty, [signedPayment, stakeDelegation], second)
+  ).toBe(true);
+  expect(
+    clientWasm.verifyReceiptChain(empty, [stakeDelegation, signedPayment], second)
+  ).toBe(false);
+  expect(clientWasm.verifyReceiptChain(first, [], first)).toBe(true);
+  expect(() => clientWasm.receiptChainHash(keypair.publicKey, payment)).toThrow(
+    "Invalid receipt chain hash"
+  );
+});
+
 test("signedRosettaTransactionToSignedCommand - Payment", () => {
   const signedRosettaTransaction = {
     signature:
@@ -197,3 +570,889 @@ test("signedRosettaTransactionToSignedCommand - StakeDelegation", () => {
     JSON.parse(signedGraphQLCommandWasm)
   );
 });
+
+test("signedCommandToRosettaTransaction", () => {
+  const signedRosettaTransaction = {
+    signature:
+      "389ac7d4077f3d485c1494782870979faa222cd906b25b2687333a92f41e40b925adb08705eddf2a7098e5ac9938498e8a0ce7c70b25ea392f4846b854086d43",
+    payment: {
+      to: "B62qnzbXmRNo9q32n4SNu2mpB8e7FYYLH8NmaX6oFCBYjjQ8SbD7uzV",
+      from: "B62qnzbXmRNo9q32n4SNu2mpB8e7FYYLH8NmaX6oFCBYjjQ8SbD7uzV",
+      fee: "10000000",
+      token: "1",
+      nonce: "0",
+      memo: "memo",
+      amount: "1000000000",
+      valid_until: "4294967295",
+    },
+    stake_delegation: null,
+    create_token: null,
+    create_token_account: null,
+    mint_tokens: null,
+  };
+  const graphQL = clientWasm.signedRosettaTransactionToSignedCommand(
+    JSON.stringify(signedRosettaTransaction)
+  );
+  expect(
+    JSON.parse(clientWasm.signedCommandToRosettaTransaction(graphQL))
+  ).toEqual(signedRosettaTransaction);
+  // the parsed command, without the `data` wrapper
+  expect(
+    JSON.parse(
+      clientWasm.signedCommandToRosettaTransaction(JSON.parse(graphQL).data)
+    )
+  ).toEqual(signedRosettaTransaction);
+
+  const keypair = clientWasm.genKeys();
+  const signedDelegation = clientWasm.signStakeDelegation(
+    {
+      to: keypair.publicKey,
+      from: keypair.publicKey,
+      fee: "0.01 MINA",
+      nonce: 7,
+    },
+    keypair.privateKey
+  );
+  const rosetta = JSON.parse(
+    clientWasm.signedCommandToRosettaTransaction(signedDelegation)
+  );
+  expect(rosetta).toEqual({
+    signature: signatureToRosettaHex(signedDelegation.signature),
+    payment: null,
+    stake_delegation: {
+      delegator: keypair.publicKey,
+      new_delegate: keypair.publicKey,
+      fee: "10000000",
+      nonce: "7",
+      memo: null,
+      valid_until: "4294967295",
+    },
+    create_token: null,
+    create_token_account: null,
+    mint_tokens: null,
+  });
+  // and back again
+  expect(
+    JSON.parse(
+      clientWasm.signedRosettaTransactionToSignedCommand(JSON.stringify(rosetta))
+    ).data.signature
+  ).toBe(signatureToBase58(signedDelegation.signature));
+  expect(() => clientWasm.signedCommandToRosettaTransaction("{}")).toThrow(
+    "Invalid signed command"
+  );
+});
+
+test("Keyring", () => {
+  const keyring = new Keyring({ network: "mainnet" });
+  const keypair = clientJs.genKeys();
+  const imported = keyring.importKey(keypair.privateKey);
+  expect(imported.publicKey).toBe(keypair.publicKey);
+  expect(keyring.exportKey(imported.keyId)).toBe(keypair.privateKey);
+
+  const generated = keyring.generateKey();
+  expect(keyring.keys()).toHaveLength(2);
+  expect(keyring.publicKey(generated.keyId)).toBe(generated.publicKey);
+
+  const payment = {
+    to: generated.publicKey,
+    from: imported.publicKey,
+    fee: 1,
+    amount: 1,
+    nonce: 0,
+    memo: "memo",
+  };
+  const signedPayment = keyring.signPayment(payment, imported.keyId);
+  expect(signedPayment).toEqual(
+    clientWasm.signPayment(payment, keypair.privateKey)
+  );
+  expect(clientJs.verifyPayment(signedPayment)).toBe(true);
+
+  const signedMessage = keyring.signMessage("hello", generated.keyId);
+  expect(clientJs.verifyMessage(signedMessage)).toBe(true);
+
+  expect(keyring.removeKey(imported.keyId)).toBe(true);
+  expect(keyring.removeKey(imported.keyId)).toBe(false);
+  expect(() => keyring.signPayment(payment, imported.keyId)).toThrow();
+  keyring.free();
+});
+
+test("signing policy", () => {
+  const keypair = clientWasm.genKeys();
+  const allowed = clientWasm.genKeys().publicKey;
+  const client = new ClientWasm({
+    network: "mainnet",
+    policy: JSON.stringify({
+      maxAmount: 100,
+      maxFee: "10",
+      allowedRecipients: [allowed],
+      memoPatterns: ["^invoice-[0-9]+$"],
+      maxValidUntil: 1000,
+      rateLimit: { maxSignatures: 2, intervalMs: 60000 },
+    }),
+  });
+  const payment = {
+    to: allowed,
+    from: keypair.publicKey,
+    fee: 1,
+    amount: 100,
+    nonce: 0,
+    memo: "invoice-1",
+    validUntil: 1000,
+  };
+  const sign = (overrides: object) =>
+    client.signPayment({ ...payment, ...overrides }, keypair.privateKey);
+
+  expect(() => sign({ amount: 101 })).toThrow("Policy violation: amount");
+  expect(() => sign({ fee: 11n })).toThrow("Policy violation: fee");
+  expect(() => sign({ to: keypair.publicKey })).toThrow("is not allowed");
+  expect(() => sign({ memo: "hello" })).toThrow("memoPatterns");
+  expect(() => sign({ validUntil: undefined })).toThrow("maxValidUntil");
+
+  expect(clientJs.verifyPayment(sign({}))).toBe(true);
+  expect(clientJs.verifyPayment(sign({ nonce: 1 }))).toBe(true);
+  expect(() => sign({ nonce: 2 })).toThrow("signatures per 60000ms");
+
+  expect(
+    () =>
+      new ClientWasm({
+        network: "mainnet",
+        policy: JSON.stringify({ maxAmont: 1 }),
+      })
+  ).toThrow("Invalid signing policy");
+
+  // perl classes and case insensitive patterns are supported
+  const perl = new ClientWasm({
+    network: "mainnet",
+    policy: { memoPatterns: ["(?i)^INVOICE-\\d+$", "^\\w+\\s\\w+$"] },
+  });
+  const signPerl = (memo: string) =>
+    perl.signPayment({ ...payment, memo }, keypair.privateKey);
+  expect(clientJs.verifyPayment(signPerl("invoice-42"))).toBe(true);
+  expect(clientJs.verifyPayment(signPerl("déjà vu"))).toBe(true);
+  expect(() => signPerl("invoice-x")).toThrow("memoPatterns");
+  // patterns match the whole memo
+  const anchored = new ClientWasm({
+    network: "mainnet",
+    policy: { memoPatterns: ["invoice-\\d+"] },
+  });
+  const signAnchored = (memo: string) =>
+    anchored.signPayment({ ...payment, memo }, keypair.privateKey);
+  expect(clientJs.verifyPayment(signAnchored("invoice-1"))).toBe(true);
+  expect(() => signAnchored("xxinvoice-1 refund")).toThrow("memoPatterns");
+  // memos are not cut to 32 bytes before the patterns are evaluated
+  expect(() => signAnchored(`invoice-${"1".repeat(30)}`)).toThrow(
+    "memo is 38 bytes, memoPatterns only accept memos of at most 32 bytes"
+  );
+  // scripts and general categories are left out
+  expect(
+    () =>
+      new ClientWasm({
+        network: "mainnet",
+        policy: { memoPatterns: ["\\p{Greek}"] },
+      })
+  ).toThrow("Invalid signing policy");
+});
+
+test("validatePayment, validateStakeDelegation and strict mode", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: "0.01 MINA",
+    amount: "1 MINA",
+    nonce: 0,
+  };
+  expect(clientWasm.validatePayment(payment)).toEqual([]);
+  const codes = (p: object) =>
+    clientWasm.validatePayment({ ...payment, ...p }).map((v) => v.code);
+  expect(codes({ fee: 1 })).toEqual(["feeTooLow"]);
+  expect(codes({ amount: 0 })).toEqual(["zeroAmount"]);
+  expect(codes({ amount: 2n ** 64n - 1n })).toEqual(["amountOverflow"]);
+  expect(codes({ nonce: 2 ** 32 - 1 })).toEqual([]);
+  expect(codes({ validUntil: 0 })).toEqual(["expired"]);
+  // validUntil is checked against the current slot unless the sender has one
+  expect(codes({ validUntil: 1000 })).toEqual(["expired"]);
+  const atSlot = (globalSlot: number) =>
+    clientWasm
+      .validatePayment(
+        { ...payment, validUntil: 1000 },
+        { balance: "10 MINA", globalSlot }
+      )
+      .map((v) => v.code);
+  expect(atSlot(1000)).toEqual([]);
+  expect(atSlot(1001)).toEqual(["expired"]);
+  expect(codes({ memo: "a".repeat(33) })).toEqual(["memoTooLong"]);
+  expect(codes({ to: "B62qinvalid" })).toEqual(["invalidPublicKey"]);
+
+  const delegation = { ...payment, amount: undefined };
+  expect(clientWasm.validateStakeDelegation(delegation)).toEqual([]);
+  expect(
+    clientWasm.validateStakeDelegation({ ...delegation, fee: 0 })[0]
+  ).toEqual({
+    code: "feeTooLow",
+    field: "fee",
+    message: "fee 0 is below the minimum of 1000000 nanomina",
+  });
+
+  const strictClient = new ClientWasm({ network: "mainnet", strict: true });
+  expect(
+    strictClient.verifyPayment(
+      strictClient.signPayment(payment, keypair.privateKey)
+    )
+  ).toBe(true);
+  expect(() =>
+    strictClient.signPayment({ ...payment, fee: 1 }, keypair.privateKey)
+  ).toThrow("Invalid payment: fee 1 is below the minimum");
+  expect(() =>
+    strictClient.signStakeDelegation(
+      { ...delegation, validUntil: 0 },
+      keypair.privateKey
+    )
+  ).toThrow("Invalid stake delegation: validUntil 0");
+  // memos are checked before they are cut to 32 bytes
+  expect(() =>
+    strictClient.signPayment(
+      { ...payment, memo: "a".repeat(40) },
+      keypair.privateKey
+    )
+  ).toThrow("Invalid payment: memo is 40 bytes");
+  expect(() =>
+    strictClient.signStakeDelegation(
+      { ...delegation, memo: "a".repeat(40) },
+      keypair.privateKey
+    )
+  ).toThrow("Invalid stake delegation: memo is 40 bytes");
+
+  // long memos are signed as their first 32 bytes, whatever their length
+  const signature = (memo: string) =>
+    clientWasm.signPayment({ ...payment, memo }, keypair.privateKey).signature;
+  for (const length of [255, 256, 257]) {
+    const memo = "a".repeat(length);
+    expect(signature(memo)).toEqual(signature("a".repeat(32)));
+    expect(
+      clientWasm.describeTransaction({ ...payment, memo }).memo
+    ).toBe("a".repeat(32));
+  }
+});
+
+test("timed accounts", () => {
+  const timing = {
+    initialMinimumBalance: "1000 MINA",
+    cliffTime: 100,
+    cliffAmount: "500 MINA",
+    vestingPeriod: 10,
+    vestingIncrement: "50 MINA",
+  };
+  expect(timedBalanceAt(timing, 99)).toEqual({
+    locked: 1_000_000_000_000n,
+    vested: 0n,
+  });
+  expect(timedBalanceAt(timing, 125, "1200 MINA")).toEqual({
+    locked: 400_000_000_000n,
+    vested: 600_000_000_000n,
+    spendable: 800_000_000_000n,
+  });
+  expect(timedBalanceAt(timing, 1_000, 0).locked).toBe(0n);
+  expect(timedBalanceAt({ ...timing, vestingPeriod: 0 }, 100).locked).toBe(0n);
+
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: "0.01 MINA",
+    amount: "900 MINA",
+    nonce: 0,
+  };
+  const sender = { balance: "1200 MINA", timing, globalSlot: 125 };
+  expect(
+    clientWasm.validatePayment(payment, sender).map((v) => v.code)
+  ).toEqual(["lockedBalance"]);
+  expect(
+    clientWasm.validatePayment({ ...payment, amount: "700 MINA" }, sender)
+  ).toEqual([]);
+  expect(
+    clientWasm
+      .validatePayment(payment, { balance: "900 MINA" })
+      .map((v) => v.code)
+  ).toEqual(["insufficientBalance"]);
+  expect(
+    clientWasm.validateStakeDelegation(
+      { to: payment.to, from: payment.from, fee: "0.01 MINA", nonce: 0 },
+      { balance: 0 }
+    )
+  ).toEqual([
+    expect.objectContaining({ code: "insufficientBalance", field: "fee" }),
+  ]);
+});
+
+test("detailed verification reports", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: 1,
+    amount: 2,
+    nonce: 3,
+  };
+  const signed = clientWasm.signPayment(payment, keypair.privateKey);
+  expect(clientWasm.verifyPaymentDetailed(signed)).toEqual({ valid: true });
+  const code = (s: any) => clientWasm.verifyPaymentDetailed(s).failure?.code;
+  expect(code({ ...signed, data: { ...payment, from: "B62qinvalid" } })).toBe(
+    "invalidPublicKey"
+  );
+  expect(code({ ...signed, data: { ...payment, amount: 3 } })).toBe(
+    "invalidSignature"
+  );
+  expect(code({ ...signed, data: { ...payment, fee: 1.5 } })).toBe(
+    "malformedInput"
+  );
+  expect(
+    code({ ...signed, signature: { ...signed.signature, field: "0x1" } })
+  ).toBe("invalidSignatureField");
+  // The pallas scalar field modulus
+  const q =
+    "28948022309329048855892746252171976963363056481941647379679742748393362948097";
+  expect(
+    code({ ...signed, signature: { ...signed.signature, scalar: q } })
+  ).toBe("invalidSignatureScalar");
+  expect(
+    new ClientWasm({ network: "testnet" }).verifyPaymentDetailed(signed)
+  ).toEqual({
+    valid: false,
+    failure: {
+      code: "wrongNetwork",
+      message: "Signature is valid on mainnet, not on testnet",
+    },
+  });
+
+  const signedMessage = clientWasm.signMessage("hello", keypair);
+  expect(clientWasm.verifyMessageDetailed(signedMessage).valid).toBe(true);
+  expect(
+    clientWasm.verifyMessageDetailed({
+      ...signedMessage,
+      data: { ...signedMessage.data, message: "bye" },
+    }).failure?.code
+  ).toBe("payloadMismatch");
+
+  const { amount, ...delegation } = payment;
+  const signedDelegation = clientWasm.signStakeDelegation(
+    delegation,
+    keypair.privateKey
+  );
+  expect(
+    clientWasm.verifyStakeDelegationDetailed(signedDelegation).valid
+  ).toBe(true);
+});
+
+test("signature encodings", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: keypair.publicKey,
+    from: keypair.publicKey,
+    fee: 1,
+    amount: 2,
+    nonce: 3,
+  };
+  const signed = clientWasm.signPayment(payment, keypair.privateKey);
+  const base58 = signatureToBase58(signed.signature);
+  const hex = signatureToRosettaHex(signed.signature);
+  const bytes = signatureToBytes(signed.signature);
+  expect(hex).toMatch(/^[0-9a-f]{128}$/);
+  expect(bytes).toBeInstanceOf(Uint8Array);
+  expect(bytes.length).toBe(64);
+  expect(signatureFromBase58(base58)).toEqual(signed.signature);
+  expect(signatureFromRosettaHex(hex)).toEqual(signed.signature);
+  expect(signatureFromBytes(bytes)).toEqual(signed.signature);
+  // conversions accept any encoding
+  expect(signatureToBase58(hex)).toBe(base58);
+  expect(signatureToRosettaHex(bytes)).toBe(hex);
+
+  for (const signature of [base58, hex, bytes]) {
+    expect(clientWasm.verifyPayment({ signature, data: payment })).toBe(true);
+    expect(
+      clientWasm.verifyPaymentDetailed({ signature, data: payment })
+    ).toEqual({ valid: true });
+  }
+  expect(
+    clientWasm.verifyPayment({ signature: base58, data: { ...payment, nonce: 4 } })
+  ).toBe(false);
+  expect(() => signatureFromBase58("abc")).toThrow("Invalid base58 signature");
+  expect(() => signatureFromBytes(new Uint8Array(63))).toThrow(
+    "Invalid signature length"
+  );
+
+  const signedMessage = clientWasm.signMessage("hello", keypair);
+  expect(
+    clientWasm.verifyMessage({
+      publicKey: keypair.publicKey,
+      data: "hello",
+      signature: signatureToBase58(signedMessage.signature.signature),
+    })
+  ).toBe(true);
+});
+
+test("describeTransaction", () => {
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: keypair.publicKey,
+    from: keypair.publicKey,
+    fee: 2_000_000_000n,
+    amount: "1500000000",
+    nonce: 3,
+    memo: "a memo that is definitely longer than 32 bytes",
+    validUntil: 1000,
+  };
+  const summary = clientWasm.describeTransaction(
+    clientWasm.signPayment(payment, keypair.privateKey)
+  );
+  expect(summary).toMatchObject({
+    kind: "payment",
+    network: "mainnet",
+    from: keypair.publicKey,
+    to: keypair.publicKey,
+    amount: "1.500000000",
+    fee: "2.000000000",
+    nonce: 3,
+    memo: "a memo that is definitely longer",
+    validUntil: 1000,
+    signed: true,
+  });
+  expect(summary.warnings.map((w) => w.code)).toEqual([
+    "selfPayment",
+    "highFee",
+    "feeExceedsAmount",
+    "memoTruncated",
+  ]);
+
+  const delegation = clientWasm.describeTransaction({
+    to: clientWasm.genKeys().publicKey,
+    from: keypair.publicKey,
+    fee: 10_000_000,
+    nonce: 0,
+  });
+  expect(delegation).toMatchObject({
+    kind: "stakeDelegation",
+    fee: "0.010000000",
+    signed: false,
+    warnings: [],
+  });
+  expect(delegation.amount).toBeUndefined();
+  expect(delegation.validUntil).toBeUndefined();
+
+  // an invalid amount is reported, not read as a stake delegation
+  expect(() =>
+    clientWasm.describeTransaction({ ...payment, amount: "1.5" })
+  ).toThrow("Invalid amount: '1.5' is not an integer");
+  expect(() =>
+    clientWasm.describeTransaction({
+      data: { ...payment, amount: "1.5" },
+      signature: { field: "1", scalar: "1" },
+    })
+  ).toThrow("Invalid data.amount");
+  expect(() =>
+    clientWasm.describeTransaction({
+      to: keypair.publicKey,
+      from: keypair.publicKey,
+      fee: 10_000_000,
+      nonce: 0,
+      amout: "1",
+    })
+  ).toThrow("unknown field `amout`");
+});
+
+test("simulateLedger", () => {
+  const [a, b, c, t] = [1, 2, 3, 4].map(() => clientWasm.genKeys());
+  const ledger = {
+    accounts: [
+      { pk: a.publicKey, balance: "10" },
+      { pk: b.publicKey, balance: "1", nonce: "0", delegate: a.publicKey },
+      {
+        pk: t.publicKey,
+        balance: "100",
+        timing: {
+          initial_minimum_balance: "100",
+          cliff_time: "10",
+          cliff_amount: "50",
+          vesting_period: "5",
+          vesting_increment: "10",
+        },
+      },
+    ],
+  };
+  const fee = 10_000_000;
+  const pay = (
+    from: typeof a,
+    to: string,
+    amount: string,
+    nonce: number,
+    validUntil?: number
+  ) =>
+    clientWasm.signPayment(
+      { from: from.publicKey, to, amount, fee, nonce, validUntil },
+      from.privateKey
+    );
+  const forged = pay(b, b.publicKey, "1 MINA", 0);
+  const transactions = [
+    pay(a, b.publicKey, "2 MINA", 0),
+    pay(a, c.publicKey, "0.5 MINA", 1),
+    // failed payments still charge the fee and bump the nonce
+    pay(a, c.publicKey, "3 MINA", 2),
+    pay(a, b.publicKey, "1 MINA", 5),
+    pay(a, b.publicKey, "1 MINA", 0),
+    pay(a, b.publicKey, "100 MINA", 3),
+    clientWasm.signStakeDelegation(
+      { from: a.publicKey, to: b.publicKey, fee, nonce: 4 },
+      a.privateKey
+    ),
+    // 50 MINA are locked at slot 12
+    pay(t, b.publicKey, "60 MINA", 0),
+    pay(t, b.publicKey, "40 MINA", 1),
+    pay(a, b.publicKey, "1 MINA", 5, 5),
+    { ...forged, data: { ...forged.data, from: a.publicKey, nonce: 5 } },
+    pay(a, b.publicKey, "1 MINA", 5),
+  ];
+  const simulation = clientWasm.simulateLedger(
+    JSON.stringify({ ledger }),
+    transactions,
+    12
+  );
+  expect(simulation.globalSlot).toBe(12);
+  expect(
+    simulation.transactions.map(
+      (t) => t.rejection?.code ?? t.failure?.code ?? "applied"
+    )
+  ).toEqual([
+    "applied",
+    "amountBelowAccountCreationFee",
+    "applied",
+    "nonceGap",
+    "staleNonce",
+    "insufficientFunds",
+    "applied",
+    "lockedBalance",
+    "applied",
+    "expired",
+    "invalidSignature",
+    "applied",
+  ]);
+  expect(simulation.transactions.map((t) => t.applied)).toEqual([
+    true,
+    true,
+    true,
+    false,
+    false,
+    true,
+    true,
+    true,
+    true,
+    false,
+    false,
+    true,
+  ]);
+  expect(simulation.changes).toEqual([
+    {
+      publicKey: a.publicKey,
+      created: false,
+      balanceBefore: "10.000000000",
+      balanceAfter: "3.940000000",
+      nonceBefore: 0,
+      nonceAfter: 6,
+      delegateBefore: a.publicKey,
+      delegateAfter: b.publicKey,
+    },
+    {
+      publicKey: b.publicKey,
+      created: false,
+      balanceBefore: "1.000000000",
+      balanceAfter: "44.000000000",
+      nonceBefore: 0,
+      nonceAfter: 0,
+      delegateBefore: a.publicKey,
+      delegateAfter: a.publicKey,
+    },
+    {
+      publicKey: t.publicKey,
+      created: false,
+      balanceBefore: "100.000000000",
+      balanceAfter: "59.980000000",
+      nonceBefore: 0,
+      nonceAfter: 2,
+      delegateBefore: t.publicKey,
+      delegateAfter: t.publicKey,
+    },
+    {
+      publicKey: c.publicKey,
+      created: true,
+      balanceAfter: "2.000000000",
+      nonceAfter: 0,
+      delegateAfter: c.publicKey,
+    },
+  ]);
+
+  // Past the cliff and 5 vesting periods everything is unlocked
+  const later = clientWasm.simulateLedger(
+    ledger,
+    [pay(t, b.publicKey, "99 MINA", 0)],
+    35
+  );
+  expect(later.transactions[0].applied).toBe(true);
+  expect(later.transactions[0].failure).toBeUndefined();
+  // the last nonce cannot be bumped
+  const exhausted = clientWasm.simulateLedger(
+    { accounts: [{ pk: a.publicKey, balance: "10", nonce: "4294967295" }] },
+    [pay(a, b.publicKey, "1 MINA", 4294967295)],
+    0
+  );
+  expect(exhausted.transactions[0].applied).toBe(false);
+  expect(exhausted.transactions[0].rejection?.code).toBe("nonceOverflow");
+  expect(() =>
+    clientWasm.simulateLedger({ accounts: [{ pk: "B62", balance: "1" }] }, [])
+  ).toThrow("Invalid accounts[0].pk");
+});
+
+test("auditPrecomputedBlock", () => {
+  const sender = clientWasm.genKeys();
+  const receiver = clientWasm.genKeys();
+  // empty memo, the bytes tag then a zero length
+  const memo = encodeBase58Check("memo", new Uint8Array(34).fill(1, 0, 1));
+  const payment = {
+    to: receiver.publicKey,
+    from: sender.publicKey,
+    fee: "0.01 MINA",
+    amount: "1.5 MINA",
+    nonce: 3,
+    validUntil: 1000,
+  };
+  const signedPayment = clientWasm.signPayment(payment, sender.privateKey);
+  const signedDelegation = clientWasm.signStakeDelegation(
+    {
+      to: receiver.publicKey,
+      from: sender.publicKey,
+      fee: "0.01 MINA",
+      nonce: 4,
+      validUntil: 1000,
+    },
+    sender.privateKey
+  );
+  const testnetPayment = new ClientWasm({
+    network: "testnet",
+  }).signPayment({ ...payment, nonce: 5 }, sender.privateKey);
+  const common = (nonce: number) => ({
+    fee: "0.01",
+    fee_token: "1",
+    fee_payer_pk: sender.publicKey,
+    nonce: `${nonce}`,
+    valid_until: "1000",
+    memo,
+  });
+  const paymentCommand = (signature: string, nonce: number) => [
+    "Signed_command",
+    {
+      payload: {
+        common: common(nonce),
+        body: [
+          "Payment",
+          {
+            source_pk: sender.publicKey,
+            receiver_pk: receiver.publicKey,
+            token_id: "1",
+            amount: "1500000000",
+          },
+        ],
+      },
+      signer: sender.publicKey,
+      signature,
+    },
+  ];
+  const delegationCommand = [
+    "Signed_command",
+    {
+      payload: {
+        common: common(4),
+        body: [
+          "Stake_delegation",
+          [
+            "Set_delegate",
+            { delegator: sender.publicKey, new_delegate: receiver.publicKey },
+          ],
+        ],
+      },
+      signer: sender.publicKey,
+      signature: signatureToBase58(signedDelegation.signature),
+    },
+  ];
+  const applied = {
+    data: paymentCommand(signatureToBase58(signedPayment.signature), 3),
+    status: ["Applied", {}, {}],
+  };
+  const block = {
+    protocol_state: {
+      body: {
+        consensus_state: {
+          blockchain_length: "100",
+          global_slot_since_genesis: "900",
+        },
+      },
+    },
+    staged_ledger_diff: {
+      diff: [
+        {
+          commands: [
+            applied,
+            {
+              data: delegationCommand,
+              status: ["Failed", [["Receiver_not_present"]], {}],
+            },
+          ],
+        },
+        {
+          commands: [
+            {
+              data: paymentCommand(
+                signatureToBase58(testnetPayment.signature),
+                5
+              ),
+              status: ["Applied"],
+            },
+            { data: ["Zkapp_command", {}], status: ["Applied"] },
+          ],
+        },
+      ],
+    },
+  };
+
+  const audit = clientWasm.auditPrecomputedBlock(JSON.stringify(block));
+  expect(audit.blockHeight).toBe(100);
+  expect(audit.globalSlot).toBe(900);
+  expect(audit.valid).toBe(false);
+  expect(audit.commands[0]).toEqual({
+    index: 0,
+    kind: "payment",
+    from: sender.publicKey,
+    to: receiver.publicKey,
+    nonce: 3,
+    hash: clientWasm.hashPayment(signedPayment),
+    status: "applied",
+    signatureValid: true,
+    issues: [],
+  });
+  expect(audit.commands[1]).toMatchObject({
+    kind: "stakeDelegation",
+    hash: clientWasm.hashStakeDelegation(signedDelegation),
+    status: "failed",
+    failures: ["Receiver_not_present"],
+    signatureValid: true,
+    issues: [],
+  });
+  expect(audit.commands[2].signatureValid).toBe(false);
+  expect(audit.commands[2].issues.map((i) => i.code)).toEqual([
+    "wrongNetwork",
+  ]);
+  expect(audit.commands[3].issues.map((i) => i.code)).toEqual([
+    "unsupportedCommand",
+  ]);
+
+  // parsed blocks are accepted too
+  const late = clientWasm.auditPrecomputedBlock({
+    protocol_state: {
+      body: { consensus_state: { global_slot_since_genesis: 1001 } },
+    },
+    staged_ledger_diff: { diff: [{ commands: [applied, applied] }, null] },
+  });
+  expect(late.commands.map((c) => c.issues.map((i) => i.code))).toEqual([
+    ["expired"],
+    ["nonceSequence", "expired"],
+  ]);
+  expect(() => clientWasm.auditPrecomputedBlock("{}")).toThrow(
+    "Invalid precomputed block"
+  );
+
+  // commands serialized by mina-rs, whose JSON is the one of the archive: fee in MINA,
+  // amount in nanomina
+  const serialized = (
+    signed: Parameters<typeof clientWasm.signedCommandToRosettaTransaction>[0]
+  ) =>
+    JSON.parse(
+      clientWasm.signedRosettaTransactionToSignedCommand(
+        clientWasm.signedCommandToRosettaTransaction(signed)
+      )
+    ).data;
+  const roundTrip = clientWasm.auditPrecomputedBlock({
+    staged_ledger_diff: {
+      diff: [
+        {
+          commands: [
+            {
+              data: ["Signed_command", serialized(signedPayment)],
+              status: ["Applied"],
+            },
+            {
+              data: ["Signed_command", serialized(signedDelegation)],
+              status: ["Applied"],
+            },
+          ],
+        },
+      ],
+    },
+  });
+  expect(roundTrip.valid).toBe(true);
+  expect(roundTrip.commands.map((c) => c.hash)).toEqual([
+    clientWasm.hashPayment(signedPayment),
+    clientWasm.hashStakeDelegation(signedDelegation),
+  ]);
+  // memos from the chain are checked, their bytes need not be utf-8
+  const withMemo = (bytes: number[]) => {
+    const command = serialized(signedPayment);
+    const memoBytes = new Uint8Array(34);
+    memoBytes.set(bytes);
+    command.payload.common.memo = encodeBase58Check("memo", memoBytes);
+    return command;
+  };
+  expect(() =>
+    clientWasm.signedCommandToRosettaTransaction(withMemo([1, 200]))
+  ).toThrow("Invalid memo: length 200 exceeds 32 bytes");
+  expect(
+    JSON.parse(
+      clientWasm.signedCommandToRosettaTransaction(withMemo([1, 2, 0xff, 0xfe]))
+    ).payment.memo
+  ).toBe("\uFFFD\uFFFD");
+});
+
+test("currency helpers", () => {
+  expect(toNanomina("1.5")).toBe(1_500_000_000n);
+  expect(toNanomina("1.5 MINA")).toBe(1_500_000_000n);
+  expect(toNanomina(".000000001")).toBe(1n);
+  expect(toNanomina("2.100000000000")).toBe(2_100_000_000n);
+  expect(() => toNanomina("0.0000000001")).toThrow("Precision loss");
+  expect(() => toNanomina("1e3")).toThrow("Invalid MINA amount");
+  expect(() => toNanomina("18446744074")).toThrow("Overflow");
+
+  expect(fromNanomina(1_500_000_000n)).toBe("1.5");
+  expect(fromNanomina("0")).toBe("0");
+  expect(formatMina(1_234_567_890, 2)).toBe("1.23");
+  expect(formatMina("1999999999", 0)).toBe("2");
+  expect(formatMina(1n)).toBe("0.000000001");
+
+  expect(addNanomina("1.5 MINA", 1)).toBe(1_500_000_001n);
+  expect(() => addNanomina(2n ** 64n - 1n, 1)).toThrow("Overflow");
+  expect(() => subNanomina(1, 2)).toThrow("Underflow");
+
+  const keypair = clientWasm.genKeys();
+  const payment = {
+    to: keypair.publicKey,
+    from: keypair.publicKey,
+    fee: "0.01 MINA",
+    amount: "1.5 MINA",
+    nonce: 0,
+  };
+  const signedPayment = clientWasm.signPayment(payment, keypair.privateKey);
+  expect(signedPayment.signature).toEqual(
+    clientWasm.signPayment(
+      { ...payment, fee: 10_000_000, amount: 1_500_000_000 },
+      keypair.privateKey
+    ).signature
+  );
+  expect(() =>
+    clientWasm.signPayment({ ...payment, nonce: "1 MINA" }, keypair.privateKey)
+  ).toThrow();
+});
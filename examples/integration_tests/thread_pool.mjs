@@ -0,0 +1,18 @@
+import { readFile } from "node:fs/promises";
+import { Worker } from "node:worker_threads";
+import init, { initThreadPool } from "./pkg-mt/mina_signer_wasm.js";
+
+/**
+ * Loads the multi-threaded build (`yarn build:wasm-mt`) and starts its rayon
+ * thread pool, each rayon thread runs in a worker_thread sharing the wasm memory
+ * */
+export async function initParallel(numThreads) {
+	await init(
+		await readFile(new URL("./pkg-mt/mina_signer_wasm_bg.wasm", import.meta.url)),
+	);
+	initThreadPool(numThreads, (module, memory, thread) => {
+		new Worker(new URL("./thread_pool_worker.mjs", import.meta.url), {
+			workerData: { module, memory, thread },
+		}).unref();
+	});
+}
@@ -0,0 +1,6 @@
+import { workerData } from "node:worker_threads";
+import init, { startWorker } from "./pkg-mt/mina_signer_wasm.js";
+
+const { module, memory, thread } = workerData;
+await init(module, memory);
+startWorker(thread);
@@ -0,0 +1,16 @@
+import pool from "./pool.js";
+
+function spawn({ modulePath, ...options }) {
+    const worker = new Worker(new URL("./worker.browser.mjs", import.meta.url), { type: "module" });
+    worker.postMessage({ modulePath, options });
+    return {
+        postMessage: (message) => worker.postMessage(message),
+        onMessage: (callback) => worker.addEventListener("message", (event) => callback(event.data)),
+        onError: (callback) => worker.addEventListener("error", (event) => callback(event.error || new Error(event.message))),
+        // Browser workers have no exit event, they only stop when terminated
+        onExit: () => {},
+        terminate: () => worker.terminate(),
+    };
+}
+
+export const AsyncClient = pool.createAsyncClient(spawn, navigator.hardwareConcurrency || 4);
@@ -0,0 +1,24 @@
+import type { Client, ClientOptions } from "../browser/index";
+
+export type AsyncMethod = Exclude<
+    { [K in keyof Client]: Client[K] extends (...args: any[]) => any ? K : never }[keyof Client],
+    "free"
+>;
+
+export interface AsyncClientOptions extends ClientOptions {
+    /** Number of workers, defaults to the number of logical cores */
+    workers?: number;
+    /** Path (node) or url (browser) of the wasm-bindgen generated js module each worker loads */
+    modulePath?: string;
+}
+
+export type AsyncClient = {
+    [K in AsyncMethod]: (...args: Parameters<Client[K]>) => Promise<ReturnType<Client[K]>>;
+} & {
+    /** Runs `method` once per argument list, spread evenly across all workers, results keep the input order */
+    batch<K extends AsyncMethod>(method: K, argsList: Parameters<Client[K]>[]): Promise<ReturnType<Client[K]>[]>;
+    /** Terminates all workers, pending requests are rejected */
+    terminate(): Promise<void>;
+};
+
+export declare const AsyncClient: new (options: AsyncClientOptions) => AsyncClient;
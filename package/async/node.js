@@ -0,0 +1,23 @@
+const path = require("node:path");
+const os = require("node:os");
+const { Worker } = require("node:worker_threads");
+const { createAsyncClient } = require("./pool");
+
+const defaultModulePath = path.resolve(__dirname, "../node/index.js");
+
+function spawn({ modulePath, ...options }) {
+    const worker = new Worker(path.resolve(__dirname, "worker.node.js"), {
+        workerData: { modulePath: modulePath || defaultModulePath, options },
+    });
+    return {
+        postMessage: (message) => worker.postMessage(message),
+        onMessage: (callback) => worker.on("message", callback),
+        onError: (callback) => worker.on("error", callback),
+        onExit: (callback) => worker.on("exit", (code) => callback(new Error(`Worker exited with code ${code}`))),
+        terminate: () => worker.terminate(),
+    };
+}
+
+const AsyncClient = createAsyncClient(spawn, os.cpus().length);
+
+module.exports = { AsyncClient };
@@ -0,0 +1,226 @@
+/**
+ * Worker pool behind `AsyncClient`, shared by the node (`worker_threads`)
+ * and browser (`Worker`) entries, each worker owns its own wasm instance
+ * */
+
+const METHODS = [
+    "genKeys",
+    "verifyKeypair",
+    "derivePublicKey",
+    "publicKeyToRaw",
+    "publicKeyToRawBeta",
+    "publicKeyToPoint",
+    "publicKeyFromPoint",
+    "publicKeyToCompressed",
+    "publicKeyFromCompressed",
+    "globalSlotAt",
+    "globalSlotStart",
+    "signMessage",
+    "verifyMessage",
+    "signPayment",
+    "verifyPayment",
+    "signPaymentBatch",
+    "signStakeDelegation",
+    "verifyStakeDelegation",
+    "hashPayment",
+    "hashStakeDelegation",
+    "signedRosettaTransactionToSignedCommand",
+    "signedCommandToRosettaTransaction",
+    "describeTransaction",
+    "simulateLedger",
+    "auditPrecomputedBlock",
+    "validatePayment",
+    "validateStakeDelegation",
+    "verifyMessageDetailed",
+    "verifyPaymentDetailed",
+    "verifyStakeDelegationDetailed",
+    "receiptChainHash",
+    "verifyReceiptChain",
+];
+
+/**
+ * Runs one request against the `Client` of a worker, used on the worker side
+ * */
+function respond(client, { id, method, args, batch }) {
+    if (typeof client[method] !== "function" || !METHODS.includes(method)) {
+        return { id, error: `Unknown method '${method}'` };
+    }
+    if (batch === undefined) {
+        try {
+            return { id, result: client[method](...args) };
+        } catch (e) {
+            return { id, error: errorMessage(e) };
+        }
+    }
+    const result = [];
+    for (let i = 0; i < batch.length; i++) {
+        try {
+            result.push(client[method](...batch[i]));
+        } catch (e) {
+            return { id, error: `Batch item ${i}: ${errorMessage(e)}` };
+        }
+    }
+    return { id, result };
+}
+
+function errorMessage(e) {
+    return e instanceof Error ? e.message : String(e);
+}
+
+/**
+ * `spawn(options)` returns a worker handle
+ * `{ postMessage(message), onMessage(callback), onError(callback), onExit(callback), terminate() }`,
+ * workers that error or exit are dropped from the pool
+ * */
+class WorkerPool {
+    constructor(options, spawn, defaultSize) {
+        const { workers, ...clientOptions } = options;
+        const size = Math.max(1, Math.floor(workers || defaultSize));
+        this.nextId = 0;
+        this.jobs = new Map();
+        this.workers = [];
+        this.terminated = false;
+        this.lastError = undefined;
+        for (let i = 0; i < size; i++) {
+            const worker = { handle: spawn(clientOptions), pending: 0 };
+            worker.handle.onMessage((reply) => this.settle(worker, reply));
+            worker.handle.onError((e) => this.fail(worker, e));
+            worker.handle.onExit((e) => this.fail(worker, e));
+            this.workers.push(worker);
+        }
+    }
+
+    /** Why no request can be posted, if so */
+    unavailable() {
+        if (this.terminated) {
+            return new Error("AsyncClient has been terminated");
+        }
+        if (this.workers.length === 0) {
+            const reason = this.lastError ? `: ${errorMessage(this.lastError)}` : "";
+            return new Error(`AsyncClient has no live workers${reason}`);
+        }
+        return undefined;
+    }
+
+    run(method, args) {
+        const error = this.unavailable();
+        if (error !== undefined) {
+            return Promise.reject(error);
+        }
+        return this.post(this.idlest(), { method, args });
+    }
+
+    batch(method, argsList) {
+        const error = this.unavailable();
+        if (error !== undefined) {
+            return Promise.reject(error);
+        }
+        if (argsList.length === 0) {
+            return Promise.resolve([]);
+        }
+        const chunkSize = Math.ceil(argsList.length / this.workers.length);
+        const chunks = [];
+        for (let i = 0; i < argsList.length; i += chunkSize) {
+            chunks.push(argsList.slice(i, i + chunkSize));
+        }
+        const idle = [...this.workers].sort((a, b) => a.pending - b.pending);
+        return Promise.all(
+            chunks.map((batch, i) =>
+                this.post(idle[i], { method, batch }).catch((e) => {
+                    // Batch item indices are local to the chunk
+                    throw new Error(
+                        e.message.replace(/^Batch item (\d+)/, (_, n) => `Batch item ${i * chunkSize + Number(n)}`),
+                    );
+                }),
+            ),
+        ).then((results) => results.flat());
+    }
+
+    terminate() {
+        const workers = this.workers;
+        this.workers = [];
+        this.terminated = true;
+        for (const worker of workers) {
+            this.rejectJobs(worker, new Error("AsyncClient has been terminated"));
+        }
+        return Promise.all(workers.map((worker) => worker.handle.terminate())).then(() => undefined);
+    }
+
+    idlest() {
+        return this.workers.reduce((a, b) => (b.pending < a.pending ? b : a));
+    }
+
+    post(worker, request) {
+        const id = this.nextId++;
+        return new Promise((resolve, reject) => {
+            this.jobs.set(id, { worker, resolve, reject });
+            worker.pending++;
+            worker.handle.postMessage({ id, ...request });
+        });
+    }
+
+    settle(worker, { id, result, error }) {
+        const job = this.jobs.get(id);
+        if (job === undefined) {
+            return;
+        }
+        this.jobs.delete(id);
+        worker.pending--;
+        if (error === undefined) {
+            job.resolve(result);
+        } else {
+            job.reject(new Error(error));
+        }
+    }
+
+    /** A worker that errored or exited never answers again, it is dropped and its jobs rejected */
+    fail(worker, e) {
+        this.rejectJobs(worker, e);
+        const index = this.workers.indexOf(worker);
+        if (index !== -1) {
+            this.workers.splice(index, 1);
+            this.lastError = e;
+            Promise.resolve(worker.handle.terminate()).catch(() => {});
+        }
+    }
+
+    rejectJobs(worker, e) {
+        for (const [id, job] of this.jobs) {
+            if (job.worker === worker) {
+                this.jobs.delete(id);
+                job.reject(e);
+            }
+        }
+        worker.pending = 0;
+    }
+}
+
+/**
+ * Promise based `Client`, requests are dispatched to the least busy worker,
+ * `batch` splits a list of requests evenly across all workers
+ * */
+function createAsyncClient(spawn, defaultSize) {
+    class AsyncClient {
+        constructor(options) {
+            this.pool = new WorkerPool(options, spawn, defaultSize);
+        }
+
+        batch(method, argsList) {
+            return this.pool.batch(method, argsList);
+        }
+
+        terminate() {
+            return this.pool.terminate();
+        }
+    }
+
+    for (const method of METHODS) {
+        AsyncClient.prototype[method] = function (...args) {
+            return this.pool.run(method, args);
+        };
+    }
+
+    return AsyncClient;
+}
+
+module.exports = { createAsyncClient, respond, errorMessage };
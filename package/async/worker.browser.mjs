@@ -0,0 +1,35 @@
+import pool from "./pool.js";
+
+// The first message carries the client options, requests posted meanwhile wait for the wasm module
+const ready = new Promise((resolve, reject) => {
+    self.addEventListener(
+        "message",
+        async ({ data: { modulePath, options } }) => {
+            try {
+                const { default: init, Client } = await (modulePath ? import(modulePath) : import("../browser/index.js"));
+                await init();
+                resolve(new Client(options));
+            } catch (e) {
+                reject(e);
+            }
+        },
+        { once: true },
+    );
+});
+// Handled by every request, not reported as an unhandled rejection
+ready.catch(() => {});
+
+self.addEventListener("message", async ({ data }) => {
+    if (data.id === undefined) {
+        return;
+    }
+    let client;
+    try {
+        client = await ready;
+    } catch (e) {
+        // Every request gets the initialization error instead of waiting forever
+        self.postMessage({ id: data.id, error: `Worker initialization failed: ${pool.errorMessage(e)}` });
+        return;
+    }
+    self.postMessage(pool.respond(client, data));
+});
@@ -0,0 +1,7 @@
+const { parentPort, workerData } = require("node:worker_threads");
+const { respond } = require("./pool");
+
+const { Client } = require(workerData.modulePath);
+const client = new Client(workerData.options);
+
+parentPort.on("message", (message) => parentPort.postMessage(respond(client, message)));
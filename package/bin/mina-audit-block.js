@@ -0,0 +1,67 @@
+#!/usr/bin/env node
+const fs = require("node:fs");
+const path = require("node:path");
+const { Client } = require("../node/index.js");
+
+const USAGE = "Usage: mina-audit-block [--network mainnet|testnet] [--json] <block.json>...";
+
+function parseArgs(argv) {
+    const args = { network: "mainnet", json: false, files: [] };
+    for (let i = 0; i < argv.length; i++) {
+        const arg = argv[i];
+        if (arg === "--network") {
+            args.network = argv[++i];
+        } else if (arg.startsWith("--network=")) {
+            args.network = arg.slice("--network=".length);
+        } else if (arg === "--json") {
+            args.json = true;
+        } else if (arg === "-h" || arg === "--help") {
+            args.help = true;
+        } else {
+            args.files.push(arg);
+        }
+    }
+    return args;
+}
+
+function main() {
+    const args = parseArgs(process.argv.slice(2));
+    if (args.help || args.files.length === 0 || !["mainnet", "testnet"].includes(args.network)) {
+        console.error(USAGE);
+        return args.help ? 0 : 2;
+    }
+    const client = new Client({ network: args.network });
+    let exitCode = 0;
+    const reports = [];
+    for (const file of args.files) {
+        let audit;
+        try {
+            audit = client.auditPrecomputedBlock(fs.readFileSync(file, "utf8"));
+        } catch (e) {
+            console.error(`${file}: ${e.message}`);
+            exitCode = 1;
+            continue;
+        }
+        if (!audit.valid) {
+            exitCode = 1;
+        }
+        if (args.json) {
+            reports.push({ file, ...audit });
+            continue;
+        }
+        const name = path.basename(file);
+        const height = audit.blockHeight === undefined ? "" : ` (height ${audit.blockHeight})`;
+        console.log(`${name}${height}: ${audit.commands.length} user commands, ${audit.valid ? "ok" : "issues found"}`);
+        for (const command of audit.commands) {
+            for (const issue of command.issues) {
+                console.log(`  #${command.index} ${command.hash || ""} ${issue.code}: ${issue.message}`);
+            }
+        }
+    }
+    if (args.json) {
+        console.log(JSON.stringify(reports, null, 2));
+    }
+    return exitCode;
+}
+
+process.exitCode = main();
@@ -0,0 +1,345 @@
+use crate::*;
+use mina_serialization_types::json::SignedCommandJson;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[wasm_bindgen(typescript_custom_section)]
+const BLOCK_AUDIT: &'static str = r#"
+export type BlockAuditIssueCode =
+    | "malformedCommand"
+    | "unsupportedCommand"
+    | "invalidSignature"
+    | "wrongNetwork"
+    | "expired"
+    | "nonceSequence"
+    | "unknownStatus"
+    | "hashFailed";
+
+export interface AuditedUserCommand {
+    /** Position in the staged ledger diff, counted across both of its parts */
+    index: number;
+    /** Unset when the command could not be decoded */
+    kind?: "payment" | "stakeDelegation";
+    from?: PublicKey;
+    to?: PublicKey;
+    nonce?: number;
+    /**
+     * Recomputed from the payload, the same way as `hashPayment` / `hashStakeDelegation`,
+     * precomputed blocks do not record it
+     */
+    hash?: string;
+    /** Status recorded by the block */
+    status: "applied" | "failed" | "unknown";
+    /** Failure reasons recorded by the block for failed commands */
+    failures?: string[];
+    /** Whether the signature verifies on the network of the client */
+    signatureValid: boolean;
+    issues: { code: BlockAuditIssueCode; message: string }[];
+}
+
+export interface PrecomputedBlockAudit {
+    blockHeight?: number;
+    globalSlot?: number;
+    /** In block order */
+    commands: AuditedUserCommand[];
+    /** Set when no command has issues */
+    valid: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "string | object")]
+    pub type PrecomputedBlock;
+
+    #[wasm_bindgen(typescript_type = "PrecomputedBlockAudit")]
+    pub type PrecomputedBlockAudit;
+}
+
+/// The parts of a precomputed block that are audited, as published by the archive buckets
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrecomputedBlockData {
+    #[serde(default)]
+    pub protocol_state: Value,
+    pub staged_ledger_diff: StagedLedgerDiffData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StagedLedgerDiffData {
+    /// The second part is `null` when the block does not use it
+    pub diff: Vec<Option<StagedLedgerDiffPartData>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StagedLedgerDiffPartData {
+    #[serde(default)]
+    pub commands: Vec<CommandWithStatusData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandWithStatusData {
+    /// `["Signed_command", command]`
+    pub data: Value,
+    /// `["Applied", ...]` or `["Failed", reasons, ...]`
+    pub status: Value,
+}
+
+impl PrecomputedBlockData {
+    fn consensus_state(&self, key: &str) -> Option<u32> {
+        let value = self
+            .protocol_state
+            .pointer(&format!("/body/consensus_state/{key}"))?;
+        match value {
+            Value::String(s) => s.parse().ok(),
+            _ => value.as_u64().and_then(|v| v.try_into().ok()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockAuditIssue {
+    pub code: &'static str,
+    pub message: String,
+}
+
+fn issue(code: &'static str, message: String) -> BlockAuditIssue {
+    BlockAuditIssue { code, message }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditedUserCommandData {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failures: Option<Vec<String>>,
+    pub signature_valid: bool,
+    pub issues: Vec<BlockAuditIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecomputedBlockAuditData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_slot: Option<u32>,
+    pub commands: Vec<AuditedUserCommandData>,
+    pub valid: bool,
+}
+
+/// Decodes `["Signed_command", command]`, other commands are reported as unsupported
+fn decode_command(data: &Value) -> Result<DecodedSignedCommand, BlockAuditIssue> {
+    let command = match data.as_array().map(Vec::as_slice) {
+        Some([Value::String(tag), command]) if tag == "Signed_command" => command,
+        Some([Value::String(tag), _]) => {
+            return Err(issue(
+                "unsupportedCommand",
+                format!("{tag} commands are not audited"),
+            ))
+        }
+        _ => {
+            return Err(issue(
+                "malformedCommand",
+                "expected [\"Signed_command\", command]".into(),
+            ))
+        }
+    };
+    let command: SignedCommandJsonData = serde_json::from_value(command.clone())
+        .map_err(|e| issue("malformedCommand", e.to_string()))?;
+    DecodedSignedCommand::try_from(&command).map_err(|e| issue("malformedCommand", e))
+}
+
+/// `(status, failures)` recorded by the block
+fn recorded_status(status: &Value) -> (&'static str, Option<Vec<String>>) {
+    let status = status.as_array().map(Vec::as_slice).unwrap_or_default();
+    match status.first().and_then(Value::as_str) {
+        Some("Applied") => ("applied", None),
+        Some("Failed") => {
+            let mut failures = vec![];
+            if let Some(reasons) = status.get(1) {
+                collect_strings(reasons, &mut failures);
+            }
+            ("failed", Some(failures))
+        }
+        _ => ("unknown", None),
+    }
+}
+
+/// Failure reasons are a string in older blocks and nested lists of strings in newer ones
+fn collect_strings(value: &Value, strings: &mut Vec<String>) {
+    match value {
+        Value::String(s) => strings.push(s.clone()),
+        Value::Array(values) => values.iter().for_each(|v| collect_strings(v, strings)),
+        _ => {}
+    }
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Verifies the signature of every signed user command of a precomputed block, as JSON or
+    /// an already parsed object, and recomputes their hashes, which the block does not record
+    #[wasm_bindgen(js_name = auditPrecomputedBlock)]
+    pub fn audit_precomputed_block(
+        &self,
+        block: PrecomputedBlock,
+    ) -> Result<PrecomputedBlockAudit, JsError> {
+        let block: PrecomputedBlockData = match block.as_string() {
+            Some(json) => from_json(json.as_str()),
+            None => deserialize_js(&block),
+        }
+        .map_err(|e| JsError::new(&format!("Invalid precomputed block: {e}")))?;
+        let audit = self.client().audit_precomputed_block(&block);
+        Ok(to_js(&audit)?.unchecked_into())
+    }
+}
+
+impl ClientImpl {
+    pub fn audit_precomputed_block(
+        &self,
+        block: &PrecomputedBlockData,
+    ) -> PrecomputedBlockAuditData {
+        let global_slot = block.consensus_state("global_slot_since_genesis");
+        let commands: Vec<&CommandWithStatusData> = block
+            .staged_ledger_diff
+            .diff
+            .iter()
+            .flatten()
+            .flat_map(|part| &part.commands)
+            .collect();
+        let decoded: Vec<_> = commands
+            .iter()
+            .map(|command| decode_command(&command.data))
+            .collect();
+        let check =
+            |command: &Result<_, _>| command.as_ref().ok().map(|c| self.check_user_command(c));
+        // Signature verification dominates, it runs on the rayon thread pool with `parallel`
+        // once `initThreadPool` was called, and sequentially otherwise
+        #[cfg(feature = "parallel")]
+        let checked: Vec<_> = if parallel::thread_pool_started() {
+            decoded.par_iter().map(check).collect()
+        } else {
+            decoded.iter().map(check).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let checked: Vec<_> = decoded.iter().map(check).collect();
+        let mut next_nonces = HashMap::new();
+        let commands: Vec<_> = commands
+            .into_iter()
+            .zip(decoded)
+            .zip(checked)
+            .enumerate()
+            .map(|(index, ((command, decoded), checked))| {
+                let (status, failures) = recorded_status(&command.status);
+                let mut audited = AuditedUserCommandData {
+                    index,
+                    kind: None,
+                    from: None,
+                    to: None,
+                    nonce: None,
+                    hash: None,
+                    status,
+                    failures,
+                    signature_valid: false,
+                    issues: vec![],
+                };
+                if status == "unknown" {
+                    audited.issues.push(issue(
+                        "unknownStatus",
+                        format!("unexpected status {}", command.status),
+                    ));
+                }
+                let decoded = match decoded {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        audited.issues.push(e);
+                        return audited;
+                    }
+                };
+                let (signature, hash) = checked.expect("decoded commands are checked");
+                audited.signature_valid = signature.is_ok();
+                audited.issues.extend(signature.err());
+                let nonce = decoded.nonce();
+                // Failed commands still pay their fee and bump the nonce
+                if let Some(expected) = next_nonces.insert(decoded.from.clone(), nonce as u64 + 1) {
+                    if nonce as u64 != expected {
+                        audited.issues.push(issue(
+                            "nonceSequence",
+                            format!(
+                                "nonce {nonce} follows nonce {} of the same fee payer",
+                                expected - 1
+                            ),
+                        ));
+                    }
+                }
+                if let Some(slot) = global_slot.filter(|slot| decoded.valid_until() < *slot) {
+                    audited.issues.push(issue(
+                        "expired",
+                        format!(
+                            "valid until slot {} but included at slot {slot}",
+                            decoded.valid_until()
+                        ),
+                    ));
+                }
+                audited.kind = Some(decoded.kind());
+                audited.nonce = Some(nonce);
+                match hash {
+                    Ok(hash) => audited.hash = Some(hash),
+                    Err(e) => audited.issues.push(e),
+                }
+                audited.from = Some(decoded.from);
+                audited.to = Some(decoded.to);
+                audited
+            })
+            .collect();
+        PrecomputedBlockAuditData {
+            block_height: block.consensus_state("blockchain_length"),
+            global_slot,
+            valid: commands.iter().all(|c| c.issues.is_empty()),
+            commands,
+        }
+    }
+
+    /// The signature check and the recomputed transaction hash of a decoded command
+    fn check_user_command(
+        &self,
+        command: &DecodedSignedCommand,
+    ) -> (Result<(), BlockAuditIssue>, Result<String, BlockAuditIssue>) {
+        let verifies = |client: &ClientImpl| match &command.payload {
+            UserCommandPayload::Payment(p) => {
+                client.verify_payment_signature(&command.signature, &command.signer, p)
+            }
+            UserCommandPayload::StakeDelegation(d) => {
+                client.verify_stake_delegation_signature(&command.signature, &command.signer, d)
+            }
+        };
+        let signature = self
+            .check_signature_network(verifies)
+            .map_err(|other| match other {
+                Some(other) => issue(
+                    "wrongNetwork",
+                    format!("the signature is for {other}, not {}", self.network),
+                ),
+                None => issue("invalidSignature", "the signature does not verify".into()),
+            });
+        let json = match &command.payload {
+            UserCommandPayload::Payment(p) => SignedCommandJson::from(p.clone()),
+            UserCommandPayload::StakeDelegation(d) => SignedCommandJson::from(d.clone()),
+        };
+        let hash = hash_signed_command(json).map_err(|e| issue("hashFailed", e));
+        (signature, hash)
+    }
+}
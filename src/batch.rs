@@ -0,0 +1,351 @@
+use crate::*;
+#[cfg(feature = "hash")]
+use mina_serialization_types::json::SignedCommandJson;
+use serde::{
+    de::{self, value::StrDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
+
+#[wasm_bindgen(typescript_custom_section)]
+const PAYMENT_BATCH: &'static str = r#"
+export interface PaymentBatchRecipient {
+    to: PublicKey;
+    amount: UInt64;
+    /** Overrides the fee of the batch */
+    fee?: UInt64;
+    /** Overrides the memo of the batch */
+    memo?: string;
+}
+
+export interface PaymentBatch {
+    /** Nonce of the first payment, i.e. the current nonce of the sender account */
+    nonce: UInt32;
+    fee: UInt64;
+    memo?: string;
+    validUntil?: ValidUntil;
+    /** A list, or CSV with `to,amount[,fee][,memo]` columns and an optional header row */
+    recipients: PaymentBatchRecipient[] | string;
+}
+
+export interface PaymentBatchManifest {
+    from: PublicKey;
+    count: number;
+    firstNonce: number;
+    /** Nonce of the sender account once every payment is applied */
+    nextNonce: number;
+    totalAmount: bigint;
+    totalFees: bigint;
+    /** totalAmount + totalFees, the balance the sender needs */
+    total: bigint;
+}
+
+export interface SignedPaymentBatch {
+    /** Ordered by nonce */
+    payments: Signed<Payment>[];
+    /** Transaction hashes in the order of `payments`, only with the `hash` feature */
+    hashes?: string[];
+    manifest: PaymentBatchManifest;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PaymentBatch")]
+    pub type PaymentBatch;
+
+    #[wasm_bindgen(typescript_type = "SignedPaymentBatch")]
+    pub type SignedPaymentBatch;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentBatchData {
+    #[serde(with = "uint")]
+    pub nonce: u32,
+    #[serde(with = "uint")]
+    pub fee: u64,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default, with = "option_uint")]
+    pub valid_until: Option<u32>,
+    pub recipients: RecipientsData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentBatchRecipientData {
+    pub to: String,
+    #[serde(with = "uint")]
+    pub amount: u64,
+    #[serde(default, with = "option_uint")]
+    pub fee: Option<u64>,
+    #[serde(default)]
+    pub memo: Option<String>,
+}
+
+/// Recipients given either as a list or as CSV
+#[derive(Debug, Clone)]
+pub struct RecipientsData(pub Vec<PaymentBatchRecipientData>);
+
+struct RecipientsVisitor;
+
+impl<'de> Visitor<'de> for RecipientsVisitor {
+    type Value = RecipientsData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of recipients or CSV with to,amount[,fee][,memo] columns")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_recipients_csv(v)
+            .map(RecipientsData)
+            .map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        Vec::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(RecipientsData)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecipientsData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RecipientsVisitor)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentBatchManifestData {
+    pub from: String,
+    pub count: u32,
+    pub first_nonce: u32,
+    pub next_nonce: u32,
+    pub total_amount: u64,
+    pub total_fees: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedPaymentBatchData {
+    pub payments: Vec<SignedData<PaymentData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashes: Option<Vec<String>>,
+    pub manifest: PaymentBatchManifestData,
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Signs one payment per recipient with consecutive nonces starting at `batch.nonce`,
+    /// with a manifest of the totals the sender account needs
+    #[wasm_bindgen(js_name = signPaymentBatch)]
+    pub fn sign_payment_batch(
+        &self,
+        batch: PaymentBatch,
+        private_key: String,
+    ) -> Result<SignedPaymentBatch, JsError> {
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
+        let batch: PaymentBatchData = from_js(&self.client().resolve_valid_until(&batch)?)?;
+        let signed = self
+            .client()
+            .sign_payment_batch(&batch, &keypair)
+            .map_err(map_js_err)?;
+        Ok(to_js(&signed)?.unchecked_into())
+    }
+}
+
+impl ClientImpl {
+    pub fn sign_payment_batch(
+        &self,
+        batch: &PaymentBatchData,
+        keypair: &MinaKeypair,
+    ) -> Result<SignedPaymentBatchData, String> {
+        let from = keypair.public.into_address();
+        let recipients = &batch.recipients.0;
+        let count = u32::try_from(recipients.len())
+            .ok()
+            .filter(|count| batch.nonce.checked_add(*count).is_some())
+            .ok_or_else(|| {
+                format!(
+                    "{} payments from nonce {} overflow the nonce",
+                    recipients.len(),
+                    batch.nonce
+                )
+            })?;
+        let mut payments = Vec::with_capacity(recipients.len());
+        #[cfg(feature = "hash")]
+        let mut hashes = Vec::with_capacity(recipients.len());
+        let (mut total_amount, mut total_fees) = (0u64, 0u64);
+        for (i, (recipient, nonce)) in recipients.iter().zip(batch.nonce..).enumerate() {
+            parse_compressed_public_key(recipient.to.as_str())
+                .map_err(|e| format!("Invalid recipients[{i}].to: {e}"))?;
+            let data = PaymentData {
+                to: recipient.to.clone(),
+                from: from.clone(),
+                fee: recipient.fee.unwrap_or(batch.fee),
+                amount: recipient.amount,
+                nonce,
+                memo: recipient.memo.clone().or_else(|| batch.memo.clone()),
+                valid_until: batch.valid_until,
+            };
+            self.check_payment_data(&data)
+                .map_err(|e| format!("recipients[{i}]: {e}"))?;
+            let payment =
+                MinaPayment::try_from(&data).map_err(|_| format!("Invalid recipients[{i}]"))?;
+            total_amount = total_amount
+                .checked_add(data.amount)
+                .ok_or("Total amount exceeds the u64 range")?;
+            total_fees = total_fees
+                .checked_add(data.fee)
+                .ok_or("Total fees exceed the u64 range")?;
+            let signature = self
+                .sign_payment(&payment, keypair)
+                .map_err(|e| format!("recipients[{i}]: {e}"))?;
+            #[cfg(feature = "hash")]
+            hashes.push(hash_signed_command(SignedCommandJson::from(payment))?);
+            payments.push(SignedData {
+                signature: signature.into(),
+                data,
+            });
+        }
+        Ok(SignedPaymentBatchData {
+            payments,
+            #[cfg(feature = "hash")]
+            hashes: Some(hashes),
+            #[cfg(not(feature = "hash"))]
+            hashes: None,
+            manifest: PaymentBatchManifestData {
+                from,
+                count,
+                first_nonce: batch.nonce,
+                next_nonce: batch.nonce + count,
+                total_amount,
+                total_fees,
+                total: total_amount
+                    .checked_add(total_fees)
+                    .ok_or("Total amount and fees exceed the u64 range")?,
+            },
+        })
+    }
+}
+
+const CSV_COLUMNS: [&str; 4] = ["to", "amount", "fee", "memo"];
+
+/// Recipients from CSV rows, the first row is a header when its first cell is `to`,
+/// otherwise columns are `to,amount,fee,memo` and the last two can be left out
+fn parse_recipients_csv(csv: &str) -> Result<Vec<PaymentBatchRecipientData>, String> {
+    let mut rows = csv_rows(csv)?.into_iter().peekable();
+    let columns: Vec<String> = match rows.peek() {
+        Some((_, header)) if header[0].eq_ignore_ascii_case("to") => {
+            let (line, header) = rows.next().expect("peeked");
+            for column in &header {
+                if !CSV_COLUMNS.contains(&column.to_ascii_lowercase().as_str()) {
+                    return Err(format!("line {line}: unknown column '{column}'"));
+                }
+            }
+            if !header.iter().any(|c| c.eq_ignore_ascii_case("amount")) {
+                return Err(format!("line {line}: missing column 'amount'"));
+            }
+            header.iter().map(|c| c.to_ascii_lowercase()).collect()
+        }
+        _ => CSV_COLUMNS.iter().map(|c| c.to_string()).collect(),
+    };
+    rows.map(|(line, cells)| {
+        if cells.len() > columns.len() {
+            return Err(format!(
+                "line {line}: {} columns, expected at most {}",
+                cells.len(),
+                columns.len()
+            ));
+        }
+        let cell = |name: &str| {
+            columns
+                .iter()
+                .position(|c| c == name)
+                .and_then(|i| cells.get(i))
+                .filter(|cell| !cell.is_empty())
+        };
+        let parse_uint = |name: &str, value: &String| {
+            uint::deserialize(StrDeserializer::<de::value::Error>::new(value))
+                .map_err(|e| format!("line {line}: {name} {e}"))
+        };
+        Ok(PaymentBatchRecipientData {
+            to: cell("to")
+                .ok_or_else(|| format!("line {line}: missing to"))?
+                .clone(),
+            amount: cell("amount")
+                .ok_or_else(|| format!("line {line}: missing amount"))
+                .and_then(|amount| parse_uint("amount", amount))?,
+            fee: cell("fee").map(|fee| parse_uint("fee", fee)).transpose()?,
+            memo: cell("memo").cloned(),
+        })
+    })
+    .collect()
+}
+
+/// Splits CSV into trimmed cells with their line numbers, quoted cells may contain
+/// commas, line breaks and `""` escaped quotes, blank lines are skipped
+fn csv_rows(csv: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows = vec![];
+    let (mut row, mut cell) = (vec![], String::new());
+    let (mut line, mut row_line) = (1, 1);
+    let (mut in_quotes, mut quoted) = (false, false);
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    cell.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if !quoted && cell.trim().is_empty() => {
+                cell.clear();
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' if !in_quotes => end_cell(&mut cell, &mut quoted, &mut row),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                end_cell(&mut cell, &mut quoted, &mut row);
+                let row = std::mem::take(&mut row);
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push((row_line, row));
+                }
+                line += 1;
+                row_line = line;
+            }
+            '\n' => {
+                line += 1;
+                cell.push(c);
+            }
+            _ if quoted && !in_quotes => {
+                if !c.is_whitespace() {
+                    return Err(format!("line {line}: unexpected '{c}' after a quoted cell"));
+                }
+            }
+            _ => cell.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!("line {row_line}: unterminated quote"));
+    }
+    end_cell(&mut cell, &mut quoted, &mut row);
+    if row.len() > 1 || !row[0].is_empty() {
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
+
+fn end_cell(cell: &mut String, quoted: &mut bool, row: &mut Vec<String>) {
+    let value = std::mem::take(cell);
+    row.push(if *quoted {
+        value
+    } else {
+        value.trim().to_string()
+    });
+    *quoted = false;
+}
@@ -1,11 +1,16 @@
 use crate::*;
 use ark_ff::PrimeField;
+#[cfg(feature = "hash")]
 use blake2::digest::VariableOutput;
 use lockfree_object_pool::{SpinLockObjectPool, SpinLockReusable};
 use mina_hasher::PoseidonHasherLegacy;
-use mina_serialization_types::{json::*, v1::*};
+#[cfg(feature = "serialization")]
+use mina_serialization_types::json::*;
+#[cfg(feature = "hash")]
+use mina_serialization_types::v1::*;
 use mina_signer::{NetworkId, PubKey, Schnorr, Signer};
 use once_cell::sync::OnceCell;
+#[cfg(feature = "hash")]
 use std::io::Write;
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -38,7 +43,7 @@ impl Drop for Client {
 }
 
 impl Client {
-    fn client(&self) -> &ClientImpl {
+    pub(crate) fn client(&self) -> &ClientImpl {
         unsafe { &*self.ptr }
     }
 }
@@ -47,17 +52,10 @@ impl Client {
 impl Client {
     #[wasm_bindgen(constructor)]
     pub fn new(options: &ClientOptions) -> Result<Client, JsError> {
-        let network = options.network();
-        if network.is_empty() {
-            Err(JsError::new(
-                "Network field should not be empty, expect 'mainnet' or 'testnet'",
-            ))
-        } else {
-            let client_impl = ClientImpl::new(network);
-            Ok(Client {
-                ptr: Box::into_raw(Box::new(client_impl)),
-            })
-        }
+        let client_impl = ClientImpl::from_options(options)?;
+        Ok(Client {
+            ptr: Box::into_raw(Box::new(client_impl)),
+        })
     }
 
     pub fn free(&self) {
@@ -81,9 +79,10 @@ impl Client {
         self.client().derive_public_key(private_key)
     }
 
+    /// Decimal x and y concatenated, kept for compatibility, see `publicKeyToPoint`
     #[wasm_bindgen(js_name = publicKeyToRaw)]
     pub fn public_key_to_raw(&self, public_key: &str) -> Result<String, JsError> {
-        let pk = PubKey::from_address(public_key).map_err(map_js_err)?;
+        let pk = parse_public_key(public_key).map_err(map_js_err)?;
         let point = pk.into_point();
         Ok(format!("{}{}", point.x.into_repr(), point.y.into_repr(),))
     }
@@ -107,87 +106,121 @@ impl Client {
         }
     }
 
+    #[cfg(feature = "message")]
     #[wasm_bindgen(js_name = signMessage)]
     pub fn sign_message(
         &self,
         message: String,
         keypair: Keypair,
     ) -> Result<SignedMessage, JsError> {
-        let js_message = new_message(keypair.public_key().as_str(), message.as_str());
-        let signature = self.client().sign_message(message, &keypair.try_into()?);
-        Ok(new_signed_message(signature.into(), js_message))
+        let keypair: SecretKeypair = keypair.try_into()?;
+        let signature = self.client().sign_message(message.clone(), &keypair);
+        let signed_message = SignedMessageData::new(
+            signature,
+            MessageData {
+                public_key: keypair.public.into_address(),
+                message,
+            },
+        );
+        Ok(to_js(&signed_message)?.unchecked_into())
     }
 
+    /// Also accepts the Auro wallet shape, `strict` additionally requires
+    /// `signature.signer` / `signature.string` of `SignedMessage` to match `data`
+    #[cfg(feature = "message")]
     #[wasm_bindgen(js_name = verifyMessage)]
-    pub fn verify_message(&self, signed_message: SignedMessage) -> Result<bool, JsError> {
-        self.client().verify_message(signed_message)
+    pub fn verify_message(
+        &self,
+        signed_message: VerifiableMessage,
+        strict: Option<bool>,
+    ) -> Result<bool, JsError> {
+        self.client()
+            .verify_message(signed_message, strict.unwrap_or(false))
     }
 
+    #[cfg(feature = "payment")]
     #[wasm_bindgen(js_name = signPayment)]
     pub fn sign_payment(
         &self,
         payment: Payment,
         private_key: String,
     ) -> Result<SignedPayment, JsError> {
-        let client = self.client();
-        let keypair: MinaKeypair = new_keypair(
-            private_key.as_str(),
-            client.derive_public_key(private_key.clone())?.as_str(),
-        )
-        .try_into()?;
-        let signature = client.sign_payment(&payment.try_to_mina_payment()?, &keypair);
-        Ok(new_signed_payment(signature.into(), payment))
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
+        let payment: Payment = self
+            .client()
+            .resolve_valid_until(&payment)?
+            .unchecked_into();
+        let data = payment.to_data()?;
+        self.client()
+            .check_payment_data(&data)
+            .map_err(map_js_err)?;
+        let signature = self
+            .client()
+            .sign_payment(&(&data).try_into()?, &keypair)
+            .map_err(map_js_err)?;
+        new_signed(signature, &payment)
     }
 
+    #[cfg(feature = "payment")]
     #[wasm_bindgen(js_name = verifyPayment)]
-    pub fn verify_payment(&self, signed_payment: SignedPayment) -> Result<bool, JsError> {
-        self.client().verify_payment(signed_payment)
+    pub fn verify_payment(&self, signed_payment: VerifiablePayment) -> Result<bool, JsError> {
+        self.client()
+            .verify_payment(signed_payment.unchecked_into())
     }
 
+    #[cfg(feature = "delegation")]
     #[wasm_bindgen(js_name = signStakeDelegation)]
     pub fn sign_stake_delegation(
         &self,
         stake_delegation: StakeDelegation,
         private_key: String,
     ) -> Result<SignedStakeDelegation, JsError> {
-        let client = self.client();
-        let keypair: MinaKeypair = new_keypair(
-            private_key.as_str(),
-            client.derive_public_key(private_key.clone())?.as_str(),
-        )
-        .try_into()?;
-        let signature = client
-            .sign_stake_delegation(&stake_delegation.try_to_mina_stake_delegation()?, &keypair);
-        Ok(new_signed_stake_delegation(
-            signature.into(),
-            stake_delegation,
-        ))
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
+        let stake_delegation: StakeDelegation = self
+            .client()
+            .resolve_valid_until(&stake_delegation)?
+            .unchecked_into();
+        let data = stake_delegation.to_data()?;
+        self.client()
+            .check_stake_delegation_data(&data)
+            .map_err(map_js_err)?;
+        let signature = self
+            .client()
+            .sign_stake_delegation(&(&data).try_into()?, &keypair)
+            .map_err(map_js_err)?;
+        new_signed(signature, &stake_delegation)
     }
 
+    #[cfg(feature = "delegation")]
     #[wasm_bindgen(js_name = verifyStakeDelegation)]
     pub fn verify_stake_delegation(
         &self,
-        signed_stake_delegation: SignedStakeDelegation,
+        signed_stake_delegation: VerifiableStakeDelegation,
     ) -> Result<bool, JsError> {
         self.client()
-            .verify_stake_delegation(signed_stake_delegation)
+            .verify_stake_delegation(signed_stake_delegation.unchecked_into())
     }
 
+    #[cfg(all(feature = "hash", feature = "payment"))]
     #[wasm_bindgen(js_name = hashPayment)]
     pub fn hash_payment(&self, signed_payment: SignedPayment) -> Result<String, JsError> {
         self.client()
-            .hash_signed_command_json(signed_payment.data().try_into()?)
+            .hash_signed_command_json((&signed_payment.to_data()?.data).try_into()?)
     }
 
+    #[cfg(all(feature = "hash", feature = "delegation"))]
     #[wasm_bindgen(js_name = hashStakeDelegation)]
     pub fn hash_stake_delegation(
         &self,
         signed_stake_delegation: SignedStakeDelegation,
     ) -> Result<String, JsError> {
         self.client()
-            .hash_signed_command_json(signed_stake_delegation.data().try_into()?)
+            .hash_signed_command_json((&signed_stake_delegation.to_data()?.data).try_into()?)
     }
 
+    #[cfg(feature = "rosetta")]
     #[wasm_bindgen(js_name = signedRosettaTransactionToSignedCommand)]
     pub fn signed_rosetta_transaction_to_signed_command(
         &self,
@@ -200,158 +233,338 @@ impl Client {
         };
         serde_json::to_string(&json).map_err(map_js_err)
     }
+
+    /// The reverse of `signedRosettaTransactionToSignedCommand`, also accepts `Signed<Payment>`
+    /// and `Signed<StakeDelegation>`
+    #[cfg(feature = "rosetta")]
+    #[wasm_bindgen(js_name = signedCommandToRosettaTransaction)]
+    pub fn signed_command_to_rosetta_transaction(
+        &self,
+        signed_command: SignedCommandLike,
+    ) -> Result<String, JsError> {
+        let (payload, signature) = parse_signed_command_like(self.client(), &signed_command)?;
+        let transaction = SignedRosettaTransaction::new(&payload, &signature);
+        serde_json::to_string(&transaction).map_err(map_js_err)
+    }
 }
 
 pub struct ClientImpl {
     pub network: String,
+    #[cfg(feature = "policy")]
+    pub policy: Option<SigningPolicy>,
+    /// Refuse to sign commands with violations, see [MinaPayment::violations]
+    #[cfg(feature = "validate")]
+    pub strict: bool,
+    /// Resolves `validUntil: { expiresAt }` to a global slot
+    pub slot_time: SlotTimeData,
 }
 
 impl ClientImpl {
     pub fn new(network: String) -> Self {
-        Self { network }
+        Self {
+            slot_time: SlotTimeData::of_network(match network.as_str() {
+                "mainnet" => NetworkId::MAINNET,
+                _ => NetworkId::TESTNET,
+            }),
+            network,
+            #[cfg(feature = "policy")]
+            policy: None,
+            #[cfg(feature = "validate")]
+            strict: false,
+        }
+    }
+
+    pub fn from_options(options: &ClientOptions) -> Result<Self, JsError> {
+        let network = options.network();
+        if network.is_empty() {
+            Err(JsError::new(
+                "Network field should not be empty, expect 'mainnet' or 'testnet'",
+            ))
+        } else {
+            let mut client = Self {
+                #[cfg(feature = "policy")]
+                policy: SigningPolicy::from_options(options)?,
+                #[cfg(feature = "validate")]
+                strict: options.strict().unwrap_or(false),
+                ..Self::new(network)
+            };
+            if let Some(slot_time) = SlotTimeData::from_options(options)? {
+                client.slot_time = slot_time;
+            }
+            Ok(client)
+        }
     }
 
     pub fn gen_keys(&self) -> Keypair {
-        MinaKeypair::rand(&mut rand::rngs::OsRng).into()
+        let keypair = SecretKeypair::from(MinaKeypair::rand(&mut rand::rngs::OsRng));
+        Keypair::from(&*keypair)
     }
 
     pub fn verify_keypair(&self, keypair: Keypair) -> Result<bool, JsError> {
-        let mina_keypair: MinaKeypair = keypair.try_into()?;
+        let mina_keypair: SecretKeypair = keypair.try_into()?;
         Ok(mina_keypair.validate())
     }
 
     pub fn derive_public_key(&self, private_key: String) -> Result<String, JsError> {
-        let decoded = bs58::decode(private_key)
-            .with_check(Some(constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE))
-            .into_vec()
-            .map_err(map_js_err)?;
-        let private_key_bytes_le = &decoded[2..];
-        let private_key =
-            <CurvePoint as AffineCurve>::ScalarField::from_bytes(private_key_bytes_le)
-                .map_err(map_js_err)?;
-        let keypair = MinaKeypair::from_secret(private_key).map_err(map_js_err)?;
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
         Ok(keypair.public.into_address())
     }
 
+    #[cfg(feature = "message")]
     pub fn sign_message(&self, message: String, keypair: &MinaKeypair) -> MinaSignature {
         let mut ctx = signer_ctx_string();
         ctx.init_domain_param(self.network_id());
         ctx.sign(keypair, &message.into())
     }
 
-    pub fn verify_message(&self, signed_message: SignedMessage) -> Result<bool, JsError> {
-        let signature: MinaSignature = signed_message.signature().signature().try_into()?;
-        let data = signed_message.data();
-        let public_key = PubKey::from_address(data.public_key().as_str()).map_err(map_js_err)?;
-        let payload: StringMessage = data.message().into();
+    #[cfg(feature = "message")]
+    pub fn verify_message(
+        &self,
+        signed_message: VerifiableMessage,
+        strict: bool,
+    ) -> Result<bool, JsError> {
+        let signed_message = signed_message.to_data()?;
+        if strict && signed_message.check_wrapper().is_err() {
+            return Ok(false);
+        }
+        let (public_key, message, signature) = signed_message.parts();
+        let signature: MinaSignature = signature.try_into()?;
+        let public_key = parse_public_key(public_key).map_err(map_js_err)?;
+        let payload: StringMessage = message.to_string().into();
+        Ok(self.verify_message_signature(&signature, &public_key, &payload))
+    }
+
+    #[cfg(feature = "message")]
+    pub fn verify_message_signature(
+        &self,
+        signature: &MinaSignature,
+        public_key: &PubKey,
+        message: &StringMessage,
+    ) -> bool {
         let mut ctx = signer_ctx_string();
         ctx.init_domain_param(self.network_id());
-        Ok(ctx.verify(&signature, &public_key, &payload))
+        ctx.verify(signature, public_key, message)
+    }
+
+    /// Strict mode and memo length policy checks of a payment to sign, on the js
+    /// representation so that memos are checked before they are cut to 32 bytes
+    #[cfg(feature = "payment")]
+    #[cfg_attr(
+        not(any(feature = "validate", feature = "policy")),
+        allow(unused_variables)
+    )]
+    pub fn check_payment_data(&self, payment: &PaymentData) -> Result<(), String> {
+        #[cfg(feature = "policy")]
+        if let Some(policy) = &self.policy {
+            policy
+                .check_memo_length(payment.memo.as_deref())
+                .map_err(|e| e.to_string())?;
+        }
+        #[cfg(feature = "validate")]
+        if self.strict {
+            let global_slot = self.slot_time.slot_at(js_sys::Date::now()).ok();
+            let violations = payment.violations(global_slot);
+            if !violations.is_empty() {
+                return Err(violations_to_string("payment", &violations));
+            }
+        }
+        Ok(())
     }
 
-    pub fn sign_payment(&self, payment: &MinaPayment, keypair: &MinaKeypair) -> MinaSignature {
+    /// Same as [Self::check_payment_data]
+    #[cfg(feature = "delegation")]
+    #[cfg_attr(
+        not(any(feature = "validate", feature = "policy")),
+        allow(unused_variables)
+    )]
+    pub fn check_stake_delegation_data(
+        &self,
+        stake_delegation: &StakeDelegationData,
+    ) -> Result<(), String> {
+        #[cfg(feature = "policy")]
+        if let Some(policy) = &self.policy {
+            policy
+                .check_memo_length(stake_delegation.memo.as_deref())
+                .map_err(|e| e.to_string())?;
+        }
+        #[cfg(feature = "validate")]
+        if self.strict {
+            let global_slot = self.slot_time.slot_at(js_sys::Date::now()).ok();
+            let violations = stake_delegation.violations(global_slot);
+            if !violations.is_empty() {
+                return Err(violations_to_string("stake delegation", &violations));
+            }
+        }
+        Ok(())
+    }
+
+    /// Signs without the checks of [Self::check_payment_data]
+    #[cfg(feature = "payment")]
+    pub fn sign_payment(
+        &self,
+        payment: &MinaPayment,
+        keypair: &MinaKeypair,
+    ) -> Result<MinaSignature, String> {
+        #[cfg(feature = "policy")]
+        if let Some(policy) = &self.policy {
+            policy
+                .check_payment(payment, keypair.public.into_address().as_str())
+                .map_err(|e| e.to_string())?;
+        }
         let mut ctx = signer_ctx_payment();
         ctx.init_domain_param(self.network_id());
-        ctx.sign(keypair, payment)
+        Ok(ctx.sign(keypair, payment))
     }
 
+    #[cfg(feature = "payment")]
     pub fn verify_payment(&self, signed_payment: SignedPayment) -> Result<bool, JsError> {
-        let signature: MinaSignature = signed_payment.signature().try_into()?;
-        let payment = signed_payment.data();
-        let public_key = PubKey::from_address(payment.from().as_str()).map_err(map_js_err)?;
+        let signed_payment = signed_payment.to_data()?;
+        let signature: MinaSignature = (&signed_payment.signature).try_into()?;
+        let payment = &signed_payment.data;
+        let public_key = parse_public_key(payment.from.as_str()).map_err(map_js_err)?;
         let payload: MinaPayment = payment.try_into()?;
+        Ok(self.verify_payment_signature(&signature, &public_key, &payload))
+    }
+
+    #[cfg(feature = "payment")]
+    pub fn verify_payment_signature(
+        &self,
+        signature: &MinaSignature,
+        public_key: &PubKey,
+        payment: &MinaPayment,
+    ) -> bool {
         let mut ctx = signer_ctx_payment();
         ctx.init_domain_param(self.network_id());
-        Ok(ctx.verify(&signature, &public_key, &payload))
+        ctx.verify(signature, public_key, payment)
     }
 
+    #[cfg(feature = "delegation")]
     pub fn sign_stake_delegation(
         &self,
         stake_delegation: &MinaStakeDelegation,
         keypair: &MinaKeypair,
-    ) -> MinaSignature {
+    ) -> Result<MinaSignature, String> {
+        #[cfg(feature = "policy")]
+        if let Some(policy) = &self.policy {
+            policy
+                .check_stake_delegation(stake_delegation, keypair.public.into_address().as_str())
+                .map_err(|e| e.to_string())?;
+        }
         let mut ctx = signer_ctx_stake_delegation();
         ctx.init_domain_param(self.network_id());
-        ctx.sign(keypair, stake_delegation)
+        Ok(ctx.sign(keypair, stake_delegation))
     }
 
+    #[cfg(feature = "delegation")]
     pub fn verify_stake_delegation(
         &self,
         signed_stake_delegation: SignedStakeDelegation,
     ) -> Result<bool, JsError> {
-        let signature: MinaSignature = signed_stake_delegation.signature().try_into()?;
-        let stake_delegation = signed_stake_delegation.data();
-        let public_key =
-            PubKey::from_address(stake_delegation.from().as_str()).map_err(map_js_err)?;
+        let signed_stake_delegation = signed_stake_delegation.to_data()?;
+        let signature: MinaSignature = (&signed_stake_delegation.signature).try_into()?;
+        let stake_delegation = &signed_stake_delegation.data;
+        let public_key = parse_public_key(stake_delegation.from.as_str()).map_err(map_js_err)?;
         let payload: MinaStakeDelegation = stake_delegation.try_into()?;
+        Ok(self.verify_stake_delegation_signature(&signature, &public_key, &payload))
+    }
+
+    #[cfg(feature = "delegation")]
+    pub fn verify_stake_delegation_signature(
+        &self,
+        signature: &MinaSignature,
+        public_key: &PubKey,
+        stake_delegation: &MinaStakeDelegation,
+    ) -> bool {
         let mut ctx = signer_ctx_stake_delegation();
         ctx.init_domain_param(self.network_id());
-        Ok(ctx.verify(&signature, &public_key, &payload))
+        ctx.verify(signature, public_key, stake_delegation)
     }
 
+    #[cfg(feature = "hash")]
     pub fn hash_signed_command_json(
         &self,
         signed_command_json: SignedCommandJson,
     ) -> Result<String, JsError> {
-        let v1: SignedCommandV1 = signed_command_json.into();
-        let mut binprot_bytes = Vec::new();
-        bin_prot::to_writer(&mut binprot_bytes, &v1).map_err(map_js_err)?;
-        let binprot_bytes_bs58 = bs58::encode(&binprot_bytes[..])
-            .with_check_version(0x13)
-            .into_string();
-        let mut hasher = blake2::Blake2bVar::new(32).unwrap();
-        hasher.write_all(binprot_bytes_bs58.as_bytes()).unwrap();
-        let mut hash = hasher.finalize_boxed().to_vec();
-        hash.insert(0, hash.len() as u8);
-        hash.insert(0, 1);
-        Ok(bs58::encode(hash).with_check_version(0x12).into_string())
+        hash_signed_command(signed_command_json).map_err(map_js_err)
     }
 
+    #[cfg(feature = "rosetta")]
     pub fn signed_rosetta_transaction_to_signed_command(
         &self,
         signed_rosetta_transaction: String,
     ) -> Result<SignedCommandJson, JsError> {
-        if let Some(signed_rosetta_transaction) =
-            signed_rosetta_transaction_from_str(signed_rosetta_transaction)
+        let signed_rosetta_transaction: SignedRosettaTransaction =
+            from_json(signed_rosetta_transaction.as_str()).map_err(map_js_err)?;
+        let signature: MinaSignature =
+            (&SignatureData::from_rosetta_hex(signed_rosetta_transaction.signature.as_str())
+                .map_err(map_js_err)?)
+                .try_into()?;
+        let mut cmd: SignedCommandJson = if let Some(payment) = &signed_rosetta_transaction.payment
         {
-            let signature = signed_rosetta_transaction.signature();
-            let mut sig_field_bytes =
-                hex::decode(&signature[..(signature.len() / 2)]).map_err(map_js_err)?;
-            sig_field_bytes.reverse();
-            let rx = <CurvePoint as AffineCurve>::BaseField::from_bytes(&sig_field_bytes[..])
-                .map_err(map_js_err)?;
-            let mut sig_scalar_bytes =
-                hex::decode(&signature[(signature.len() / 2)..]).map_err(map_js_err)?;
-            sig_scalar_bytes.reverse();
-            let s = <CurvePoint as AffineCurve>::ScalarField::from_bytes(&sig_scalar_bytes[..])
-                .map_err(map_js_err)?;
-            let signature = MinaSignature { rx, s };
-            let mut cmd: SignedCommandJson = if let Some(payment) =
-                signed_rosetta_transaction.payment()
-            {
-                payment.try_into()?
-            } else if let Some(stake_delegation) = signed_rosetta_transaction.stake_delegation() {
-                stake_delegation.try_into()?
-            } else {
-                return Err(JsError::new(
-                    "Either payment or stake_delegation should be set",
-                ));
-            };
-            cmd.signature = signature_to_json(signature);
-            Ok(cmd)
+            payment.try_into()?
+        } else if let Some(stake_delegation) = &signed_rosetta_transaction.stake_delegation {
+            stake_delegation.try_into()?
         } else {
-            Err(JsError::new("Failed"))
-        }
+            return Err(JsError::new(
+                "Either payment or stake_delegation should be set",
+            ));
+        };
+        cmd.signature = signature_to_json(signature);
+        Ok(cmd)
     }
 
-    fn network_id(&self) -> NetworkId {
+    pub(crate) fn network_id(&self) -> NetworkId {
         match self.network.as_str() {
             "mainnet" => NetworkId::MAINNET,
             _ => NetworkId::TESTNET,
         }
     }
+
+    /// Verifies with this client, then with the other network to tell a wrong network apart,
+    /// the error is the other network when the signature is valid there
+    pub(crate) fn check_signature_network(
+        &self,
+        verify: impl Fn(&ClientImpl) -> bool,
+    ) -> Result<(), Option<&'static str>> {
+        if verify(self) {
+            return Ok(());
+        }
+        let other_network = match self.network_id() {
+            NetworkId::MAINNET => "testnet",
+            NetworkId::TESTNET => "mainnet",
+        };
+        if verify(&ClientImpl::new(other_network.into())) {
+            Err(Some(other_network))
+        } else {
+            Err(None)
+        }
+    }
+}
+
+/// Computes the transaction hash of a signed command,
+/// the same way as `Transaction_hash.hash_command` in the mina daemon
+#[cfg(feature = "hash")]
+pub(crate) fn hash_signed_command(
+    signed_command_json: SignedCommandJson,
+) -> Result<String, String> {
+    let v1: SignedCommandV1 = signed_command_json.into();
+    let mut binprot_bytes = Vec::new();
+    bin_prot::to_writer(&mut binprot_bytes, &v1).map_err(|e| e.to_string())?;
+    let binprot_bytes_bs58 = bs58::encode(&binprot_bytes[..])
+        .with_check_version(constants::SIGNED_COMMAND_V1_BASE58_CHECK_VERSION_BYTE)
+        .into_string();
+    let mut hasher = blake2::Blake2bVar::new(32).unwrap();
+    hasher.write_all(binprot_bytes_bs58.as_bytes()).unwrap();
+    let mut hash = hasher.finalize_boxed().to_vec();
+    hash.insert(0, hash.len() as u8);
+    hash.insert(0, 1);
+    Ok(bs58::encode(hash)
+        .with_check_version(constants::TRANSACTION_HASH_BASE58_CHECK_VERSION_BYTE)
+        .into_string())
 }
 
+#[cfg(feature = "message")]
 fn signer_ctx_string() -> SpinLockReusable<
     'static,
     Schnorr<PoseidonHasherLegacy<SchnorrMessage<StringMessage>>, StringMessage>,
@@ -375,6 +588,7 @@ fn signer_ctx_string() -> SpinLockReusable<
     pool.pull()
 }
 
+#[cfg(feature = "payment")]
 fn signer_ctx_payment() -> SpinLockReusable<
     'static,
     Schnorr<PoseidonHasherLegacy<SchnorrMessage<MinaPayment>>, MinaPayment>,
@@ -396,6 +610,7 @@ fn signer_ctx_payment() -> SpinLockReusable<
     pool.pull()
 }
 
+#[cfg(feature = "delegation")]
 fn signer_ctx_stake_delegation() -> SpinLockReusable<
     'static,
     Schnorr<PoseidonHasherLegacy<SchnorrMessage<MinaStakeDelegation>>, MinaStakeDelegation>,
@@ -0,0 +1,164 @@
+use crate::*;
+use serde::{Deserialize, Serialize, Serializer};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BASE58_CHECK: &'static str = r#"
+export type Base58Kind =
+    | "ledgerHash"
+    | "receiptChainHash"
+    | "epochSeed"
+    | "stateHash"
+    | "stateBodyHash"
+    | "transactionHash"
+    | "signedCommand"
+    | "memo"
+    | "tokenId"
+    | "privateKey"
+    | "publicKey"
+    | "signature";
+
+export interface DecodedBase58Check {
+    kind: Base58Kind;
+    versionByte: number;
+    /** Everything after the version byte, without the checksum */
+    bytes: Uint8Array;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Base58Kind")]
+    pub type Base58KindJs;
+
+    #[wasm_bindgen(typescript_type = "DecodedBase58Check")]
+    pub type DecodedBase58Check;
+}
+
+/// The kinds of base58check strings the daemon produces, identified by their version byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Base58Kind {
+    LedgerHash,
+    ReceiptChainHash,
+    EpochSeed,
+    StateHash,
+    StateBodyHash,
+    TransactionHash,
+    SignedCommand,
+    Memo,
+    TokenId,
+    PrivateKey,
+    PublicKey,
+    Signature,
+}
+
+impl Base58Kind {
+    pub const ALL: [Base58Kind; 12] = [
+        Self::LedgerHash,
+        Self::ReceiptChainHash,
+        Self::EpochSeed,
+        Self::StateHash,
+        Self::StateBodyHash,
+        Self::TransactionHash,
+        Self::SignedCommand,
+        Self::Memo,
+        Self::TokenId,
+        Self::PrivateKey,
+        Self::PublicKey,
+        Self::Signature,
+    ];
+
+    pub fn version_byte(self) -> u8 {
+        match self {
+            Self::LedgerHash => constants::LEDGER_HASH_BASE58_CHECK_VERSION_BYTE,
+            Self::ReceiptChainHash => constants::RECEIPT_CHAIN_HASH_BASE58_CHECK_VERSION_BYTE,
+            Self::EpochSeed => constants::EPOCH_SEED_BASE58_CHECK_VERSION_BYTE,
+            Self::StateHash => constants::STATE_HASH_BASE58_CHECK_VERSION_BYTE,
+            Self::StateBodyHash => constants::STATE_BODY_HASH_BASE58_CHECK_VERSION_BYTE,
+            Self::TransactionHash => constants::TRANSACTION_HASH_BASE58_CHECK_VERSION_BYTE,
+            Self::SignedCommand => constants::SIGNED_COMMAND_V1_BASE58_CHECK_VERSION_BYTE,
+            Self::Memo => constants::MEMO_BASE58_CHECK_VERSION_BYTE,
+            Self::TokenId => constants::TOKEN_ID_BASE58_CHECK_VERSION_BYTE,
+            Self::PrivateKey => constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE,
+            Self::PublicKey => constants::PUBLIC_KEY_BASE58_CHECK_VERSION_BYTE,
+            Self::Signature => constants::SIGNATURE_BASE58_CHECK_VERSION_BYTE,
+        }
+    }
+
+    pub fn from_version_byte(version_byte: u8) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.version_byte() == version_byte)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedBase58CheckData {
+    pub kind: Base58Kind,
+    pub version_byte: u8,
+    #[serde(serialize_with = "serialize_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+/// Serialized as `Uint8Array` rather than an array of numbers
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(bytes)
+}
+
+pub fn encode_base58_check(kind: Base58Kind, bytes: &[u8]) -> String {
+    bs58::encode(bytes)
+        .with_check_version(kind.version_byte())
+        .into_string()
+}
+
+/// Decodes any known base58check string, or only `expected` when it is set
+pub fn decode_base58_check(
+    value: &str,
+    expected: Option<Base58Kind>,
+) -> Result<DecodedBase58CheckData, String> {
+    let mut decoded = bs58::decode(value)
+        .with_check(expected.map(Base58Kind::version_byte))
+        .into_vec()
+        .map_err(|e| format!("Invalid base58check: {e}"))?;
+    if decoded.is_empty() {
+        return Err("Invalid base58check: missing version byte".into());
+    }
+    let version_byte = decoded.remove(0);
+    let kind = Base58Kind::from_version_byte(version_byte)
+        .ok_or_else(|| format!("Invalid base58check: unknown version byte {version_byte:#04x}"))?;
+    Ok(DecodedBase58CheckData {
+        kind,
+        version_byte,
+        bytes: decoded,
+    })
+}
+
+/// Encodes `bytes` (everything after the version byte) as a base58check string of `kind`
+#[wasm_bindgen(js_name = encodeBase58Check)]
+pub fn encode_base58_check_js(kind: Base58KindJs, bytes: &[u8]) -> Result<String, JsError> {
+    let kind: Base58Kind = from_js(&kind)?;
+    Ok(encode_base58_check(kind, bytes))
+}
+
+/// Decodes a base58check string into its kind and raw bytes,
+/// throws when the checksum is wrong or the kind is not `expected`
+#[wasm_bindgen(js_name = decodeBase58Check)]
+pub fn decode_base58_check_js(
+    value: &str,
+    expected: Option<Base58KindJs>,
+) -> Result<DecodedBase58Check, JsError> {
+    let expected: Option<Base58Kind> = match expected {
+        Some(kind) => Some(from_js(&kind)?),
+        None => None,
+    };
+    let decoded = decode_base58_check(value, expected).map_err(map_js_err)?;
+    Ok(to_js(&decoded)?.unchecked_into())
+}
+
+/// The kind of a base58check string, `undefined` when it is not one, never throws
+#[wasm_bindgen(js_name = identifyBase58Check)]
+pub fn identify_base58_check(value: JsValue) -> Option<Base58KindJs> {
+    let decoded = decode_base58_check(&value.as_string()?, None).ok()?;
+    to_js(&decoded.kind).ok().map(JsCast::unchecked_into)
+}
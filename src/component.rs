@@ -0,0 +1,192 @@
+use crate::*;
+
+wit_bindgen::generate!({
+    world: "mina-signer",
+    path: "wit",
+});
+
+use exports::chainsafe::mina_signer::signer::{
+    Guest, Keypair as WitKeypair, Network, Payment as WitPayment, Signature as WitSignature,
+    SignedMessage as WitSignedMessage, SignedPayment as WitSignedPayment,
+    SignedStakeDelegation as WitSignedStakeDelegation, StakeDelegation as WitStakeDelegation,
+};
+
+/// Component model (WASI) entry, it only relies on the js free parts of the crate
+struct Component;
+
+export!(Component);
+
+impl Guest for Component {
+    fn gen_keys() -> WitKeypair {
+        let keypair = SecretKeypair::from(MinaKeypair::rand(&mut rand::rngs::OsRng));
+        WitKeypair {
+            private_key: encode_private_key(&keypair),
+            public_key: keypair.public.into_address(),
+        }
+    }
+
+    fn verify_keypair(keypair: WitKeypair) -> Result<bool, String> {
+        let private_key = decode_private_key(keypair.private_key.as_str())?;
+        let public_key =
+            parse_public_key(keypair.public_key.as_str()).map_err(|e| e.to_string())?;
+        Ok(SecretKeypair::from_parts(&private_key, public_key.into_point()).validate())
+    }
+
+    fn derive_public_key(private_key: String) -> Result<String, String> {
+        let private_key = Zeroizing::new(private_key);
+        Ok(keypair_from_private_key(private_key.as_str())?
+            .public
+            .into_address())
+    }
+
+    fn sign_message(
+        network: Network,
+        message: String,
+        keypair: WitKeypair,
+    ) -> Result<WitSignedMessage, String> {
+        let mina_keypair = keypair_from_private_key(keypair.private_key.as_str())?;
+        if mina_keypair.public.into_address() != keypair.public_key {
+            return Err("Public key does not match the private key".into());
+        }
+        let signature = client(network).sign_message(message.clone(), &mina_keypair);
+        Ok(WitSignedMessage {
+            signature: signature.into(),
+            public_key: keypair.public_key,
+            message,
+        })
+    }
+
+    fn verify_message(network: Network, signed_message: WitSignedMessage) -> Result<bool, String> {
+        let signature: MinaSignature = (&signed_message.signature).try_into()?;
+        let public_key =
+            parse_public_key(signed_message.public_key.as_str()).map_err(|e| e.to_string())?;
+        Ok(client(network).verify_message_signature(
+            &signature,
+            &public_key,
+            &signed_message.message.into(),
+        ))
+    }
+
+    fn sign_payment(
+        network: Network,
+        payment: WitPayment,
+        private_key: String,
+    ) -> Result<WitSignedPayment, String> {
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str())?;
+        let mina_payment: MinaPayment = (&payment).try_into()?;
+        let signature = client(network).sign_payment(&mina_payment, &keypair)?;
+        Ok(WitSignedPayment {
+            signature: signature.into(),
+            data: payment,
+        })
+    }
+
+    fn verify_payment(network: Network, signed_payment: WitSignedPayment) -> Result<bool, String> {
+        let signature: MinaSignature = (&signed_payment.signature).try_into()?;
+        let public_key =
+            parse_public_key(signed_payment.data.from.as_str()).map_err(|e| e.to_string())?;
+        let payment: MinaPayment = (&signed_payment.data).try_into()?;
+        Ok(client(network).verify_payment_signature(&signature, &public_key, &payment))
+    }
+
+    fn sign_stake_delegation(
+        network: Network,
+        stake_delegation: WitStakeDelegation,
+        private_key: String,
+    ) -> Result<WitSignedStakeDelegation, String> {
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str())?;
+        let mina_stake_delegation: MinaStakeDelegation = (&stake_delegation).try_into()?;
+        let signature = client(network).sign_stake_delegation(&mina_stake_delegation, &keypair)?;
+        Ok(WitSignedStakeDelegation {
+            signature: signature.into(),
+            data: stake_delegation,
+        })
+    }
+
+    fn verify_stake_delegation(
+        network: Network,
+        signed_stake_delegation: WitSignedStakeDelegation,
+    ) -> Result<bool, String> {
+        let signature: MinaSignature = (&signed_stake_delegation.signature).try_into()?;
+        let public_key = parse_public_key(signed_stake_delegation.data.from.as_str())
+            .map_err(|e| e.to_string())?;
+        let stake_delegation: MinaStakeDelegation = (&signed_stake_delegation.data).try_into()?;
+        Ok(client(network).verify_stake_delegation_signature(
+            &signature,
+            &public_key,
+            &stake_delegation,
+        ))
+    }
+
+    fn hash_payment(signed_payment: WitSignedPayment) -> Result<String, String> {
+        // Same as `Client.hashPayment`, only the payload is hashed
+        let payment: MinaPayment = (&signed_payment.data).try_into()?;
+        hash_signed_command(payment.into())
+    }
+
+    fn hash_stake_delegation(
+        signed_stake_delegation: WitSignedStakeDelegation,
+    ) -> Result<String, String> {
+        // Same as `Client.hashStakeDelegation`, only the payload is hashed
+        let stake_delegation: MinaStakeDelegation = (&signed_stake_delegation.data).try_into()?;
+        hash_signed_command(stake_delegation.into())
+    }
+}
+
+fn client(network: Network) -> ClientImpl {
+    ClientImpl::new(
+        match network {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+        }
+        .into(),
+    )
+}
+
+impl From<MinaSignature> for WitSignature {
+    fn from(value: MinaSignature) -> Self {
+        let (field, scalar) = signature_to_decimal(&value);
+        Self { field, scalar }
+    }
+}
+
+impl TryFrom<&WitSignature> for MinaSignature {
+    type Error = String;
+
+    fn try_from(value: &WitSignature) -> Result<Self, Self::Error> {
+        signature_from_decimal(value.field.as_str(), value.scalar.as_str())
+    }
+}
+
+impl TryFrom<&WitPayment> for MinaPayment {
+    type Error = String;
+
+    fn try_from(v: &WitPayment) -> Result<Self, Self::Error> {
+        Ok(MinaPayment {
+            to: parse_compressed_public_key(v.to.as_str()).map_err(|e| e.to_string())?,
+            from: parse_compressed_public_key(v.from.as_str()).map_err(|e| e.to_string())?,
+            fee: v.fee,
+            amount: v.amount,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
+        })
+    }
+}
+
+impl TryFrom<&WitStakeDelegation> for MinaStakeDelegation {
+    type Error = String;
+
+    fn try_from(v: &WitStakeDelegation) -> Result<Self, Self::Error> {
+        Ok(MinaStakeDelegation {
+            to: parse_compressed_public_key(v.to.as_str()).map_err(|e| e.to_string())?,
+            from: parse_compressed_public_key(v.from.as_str()).map_err(|e| e.to_string())?,
+            fee: v.fee,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
+        })
+    }
+}
@@ -2,10 +2,61 @@ pub const PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE: u8 = 0x5a;
 
 pub const PUBLIC_KEY_BASE58_CHECK_VERSION_BYTE: u8 = 0xcb;
 
+pub const SIGNATURE_BASE58_CHECK_VERSION_BYTE: u8 = 0x9a;
+
+// Version bytes of the remaining base58check encodings, see `version_bytes.ml` of the daemon
+
+pub const LEDGER_HASH_BASE58_CHECK_VERSION_BYTE: u8 = 0x05;
+
+pub const RECEIPT_CHAIN_HASH_BASE58_CHECK_VERSION_BYTE: u8 = 0x0c;
+
+pub const EPOCH_SEED_BASE58_CHECK_VERSION_BYTE: u8 = 0x0d;
+
+pub const STATE_HASH_BASE58_CHECK_VERSION_BYTE: u8 = 0x10;
+
+pub const STATE_BODY_HASH_BASE58_CHECK_VERSION_BYTE: u8 = 0x11;
+
+pub const TRANSACTION_HASH_BASE58_CHECK_VERSION_BYTE: u8 = 0x12;
+
+pub const SIGNED_COMMAND_V1_BASE58_CHECK_VERSION_BYTE: u8 = 0x13;
+
+pub const MEMO_BASE58_CHECK_VERSION_BYTE: u8 = 0x14;
+
+pub const TOKEN_ID_BASE58_CHECK_VERSION_BYTE: u8 = 0x1c;
+
+#[cfg(any(feature = "payment", feature = "delegation"))]
 pub const MEMO_BYTES: usize = 34;
 
+#[cfg(any(feature = "payment", feature = "delegation"))]
 const TAG_BITS: usize = 3;
 
+#[cfg(feature = "payment")]
 pub const PAYMENT_TX_TAG: [bool; TAG_BITS] = [false, false, false];
 
+#[cfg(feature = "delegation")]
 pub const DELEGATION_TX_TAG: [bool; TAG_BITS] = [false, false, true];
+
+/// `Currency.Fee.minimum_user_command_fee` of the daemon, 0.001 MINA
+#[cfg(feature = "validate")]
+pub const MINIMUM_USER_COMMAND_FEE: u64 = 1_000_000;
+
+/// `Receipt.Chain_hash.empty` of the daemon, found on every account of the genesis ledger
+#[cfg(feature = "receipt")]
+pub const EMPTY_RECEIPT_CHAIN_HASH: &str = "2mzbV7WevxLuchs2dAMY4vQBS6XttnCUF8Hvks4XNBQ5qiSGGBQe";
+
+/// 2021-03-17T00:00:00Z, `genesis_state_timestamp` of the mainnet config
+pub const MAINNET_GENESIS_TIMESTAMP_MS: u64 = 1_615_939_200_000;
+
+/// 2020-09-16T10:15:00Z, `genesis_state_timestamp` of the devnet config
+pub const TESTNET_GENESIS_TIMESTAMP_MS: u64 = 1_600_251_300_000;
+
+/// 3 minutes on both networks
+pub const SLOT_DURATION_MS: u64 = 180_000;
+
+/// Deducted from the first payment to an account that is not in the ledger yet, 1 MINA
+#[cfg(feature = "ledger")]
+pub const ACCOUNT_CREATION_FEE: u64 = NANOMINA_PER_MINA;
+
+pub const NANOMINA_PER_MINA: u64 = 1_000_000_000;
+
+pub const MINA_DECIMALS: usize = 9;
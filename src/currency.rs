@@ -0,0 +1,113 @@
+use crate::*;
+use serde::Deserialize;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CURRENCY: &'static str = r#"
+/** Decimal MINA amount like "1.5", UInt64 fields also accept it as "1.5 MINA" */
+export type MinaAmount = string;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "UInt64")]
+    pub type UInt64;
+}
+
+#[derive(Deserialize)]
+struct Nanomina(#[serde(with = "uint")] u64);
+
+impl UInt64 {
+    pub(crate) fn to_nanomina(&self) -> Result<u64, JsError> {
+        from_js::<Nanomina>(self).map(|v| v.0)
+    }
+}
+
+/// Parses a decimal MINA amount, e.g. `1.5`, into nanomina
+pub(crate) fn parse_mina(mina: &str) -> Result<u64, String> {
+    let (int, frac) = mina.split_once('.').unwrap_or((mina, ""));
+    if (int.is_empty() && frac.is_empty())
+        || !int.bytes().all(|b| b.is_ascii_digit())
+        || !frac.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(format!(
+            "Invalid MINA amount '{mina}', expected a decimal like 1.5"
+        ));
+    }
+    let frac = frac.trim_end_matches('0');
+    if frac.len() > constants::MINA_DECIMALS {
+        return Err(format!(
+            "Precision loss: '{mina}' has more than {} decimals, 1 nanomina is 0.000000001 MINA",
+            constants::MINA_DECIMALS
+        ));
+    }
+    let overflow = || format!("Overflow: '{mina}' MINA exceeds the u64 nanomina range");
+    let int: u64 = if int.is_empty() {
+        0
+    } else {
+        int.parse().map_err(|_| overflow())?
+    };
+    let frac: u64 = format!("{frac:0<width$}", width = constants::MINA_DECIMALS)
+        .parse()
+        .expect("at most 9 ascii digits");
+    int.checked_mul(constants::NANOMINA_PER_MINA)
+        .and_then(|v| v.checked_add(frac))
+        .ok_or_else(overflow)
+}
+
+/// Formats nanomina as MINA with a fixed number of decimals (at most 9), rounding half up
+pub(crate) fn format_nanomina(nanomina: u64, decimals: usize) -> String {
+    let decimals = decimals.min(constants::MINA_DECIMALS);
+    let unit = 10u128.pow((constants::MINA_DECIMALS - decimals) as u32);
+    // u128 as rounding up u64::MAX would overflow
+    let rounded = (nanomina as u128 + unit / 2) / unit;
+    let scale = 10u128.pow(decimals as u32);
+    if decimals == 0 {
+        rounded.to_string()
+    } else {
+        format!("{}.{:0decimals$}", rounded / scale, rounded % scale)
+    }
+}
+
+/// Converts a MINA amount, e.g. `"1.5"` or `"1.5 MINA"`, into nanomina,
+/// amounts with more than 9 decimals are rejected rather than rounded
+#[wasm_bindgen(js_name = toNanomina)]
+pub fn to_nanomina(mina: &str) -> Result<u64, JsError> {
+    let mina = mina.trim();
+    parse_mina(mina.strip_suffix("MINA").unwrap_or(mina).trim_end()).map_err(map_js_err)
+}
+
+/// Converts nanomina into an exact MINA amount without trailing zeros, e.g. `"1.5"`
+#[wasm_bindgen(js_name = fromNanomina)]
+pub fn from_nanomina(nanomina: UInt64) -> Result<String, JsError> {
+    let mina = format_nanomina(nanomina.to_nanomina()?, constants::MINA_DECIMALS);
+    Ok(mina.trim_end_matches('0').trim_end_matches('.').to_string())
+}
+
+/// Formats nanomina as MINA with `decimals` decimals (9 by default), rounding half up
+#[wasm_bindgen(js_name = formatMina)]
+pub fn format_mina(nanomina: UInt64, decimals: Option<usize>) -> Result<String, JsError> {
+    let decimals = decimals.unwrap_or(constants::MINA_DECIMALS);
+    if decimals > constants::MINA_DECIMALS {
+        return Err(JsError::new(&format!(
+            "decimals should be at most {}",
+            constants::MINA_DECIMALS
+        )));
+    }
+    Ok(format_nanomina(nanomina.to_nanomina()?, decimals))
+}
+
+/// Checked `a + b` in nanomina, e.g. the total cost of `amount` and `fee`
+#[wasm_bindgen(js_name = addNanomina)]
+pub fn add_nanomina(a: UInt64, b: UInt64) -> Result<u64, JsError> {
+    let (a, b) = (a.to_nanomina()?, b.to_nanomina()?);
+    a.checked_add(b)
+        .ok_or_else(|| JsError::new(&format!("Overflow: {a} + {b} exceeds u64")))
+}
+
+/// Checked `a - b` in nanomina
+#[wasm_bindgen(js_name = subNanomina)]
+pub fn sub_nanomina(a: UInt64, b: UInt64) -> Result<u64, JsError> {
+    let (a, b) = (a.to_nanomina()?, b.to_nanomina()?);
+    a.checked_sub(b)
+        .ok_or_else(|| JsError::new(&format!("Underflow: {a} - {b} is negative")))
+}
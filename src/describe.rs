@@ -0,0 +1,158 @@
+use crate::*;
+use serde::Serialize;
+
+/// Fees at or above 1 MINA are flagged, typical fees are around 0.01 MINA
+const HIGH_FEE_NANOMINA: u64 = constants::NANOMINA_PER_MINA;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TRANSACTION_SUMMARY: &'static str = r#"
+export interface TransactionWarning {
+    code: "selfPayment" | "highFee" | "feeExceedsAmount" | "memoTruncated";
+    message: string;
+}
+
+export interface TransactionSummary {
+    kind: "payment" | "stakeDelegation";
+    network: "mainnet" | "testnet";
+    from: PublicKey;
+    /** Receiver of a payment, or the new delegate */
+    to: PublicKey;
+    /** MINA with 9 decimals, payments only */
+    amount?: string;
+    /** MINA with 9 decimals */
+    fee: string;
+    nonce: number;
+    /** Memo as it is signed, i.e. after truncation to 32 bytes */
+    memo?: string;
+    /** Last global slot the transaction is valid for, unset when it never expires */
+    validUntil?: number;
+    signed: boolean;
+    warnings: TransactionWarning[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "TransactionSummary")]
+    pub type TransactionSummary;
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWarningData {
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummaryData {
+    pub kind: &'static str,
+    pub network: String,
+    pub from: String,
+    pub to: String,
+    pub amount: Option<String>,
+    pub fee: String,
+    pub nonce: u32,
+    pub memo: Option<String>,
+    pub valid_until: Option<u32>,
+    pub signed: bool,
+    pub warnings: Vec<TransactionWarningData>,
+}
+
+impl TransactionSummaryData {
+    fn warn(&mut self, code: &'static str, message: String) {
+        self.warnings.push(TransactionWarningData { code, message });
+    }
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Summary of a payment or stake delegation, signed or not, for confirmation screens
+    #[wasm_bindgen(js_name = describeTransaction)]
+    pub fn describe_transaction(
+        &self,
+        transaction: Transaction,
+    ) -> Result<TransactionSummary, JsError> {
+        let summary = self.client().describe_transaction(&transaction)?;
+        Ok(to_js(&summary)?.unchecked_into())
+    }
+}
+
+impl ClientImpl {
+    pub fn describe_transaction(
+        &self,
+        transaction: &JsValue,
+    ) -> Result<TransactionSummaryData, JsError> {
+        let transaction =
+            TransactionData::parse(&self.resolve_transaction_valid_until(transaction)?)?;
+        let signed = transaction.is_signed();
+        let data = transaction.into_unsigned();
+        let (kind, from, to, amount, fee, nonce, memo, valid_until) = match data {
+            UnsignedTransactionData::Payment(p) => (
+                "payment",
+                p.from,
+                p.to,
+                Some(p.amount),
+                p.fee,
+                p.nonce,
+                p.memo,
+                p.valid_until,
+            ),
+            UnsignedTransactionData::StakeDelegation(d) => (
+                "stakeDelegation",
+                d.from,
+                d.to,
+                None,
+                d.fee,
+                d.nonce,
+                d.memo,
+                d.valid_until,
+            ),
+        };
+        // The memo as it ends up in the signed payload
+        let signed_memo = memo_to_string(&string_to_memo(memo.clone()));
+        let mut summary = TransactionSummaryData {
+            kind,
+            network: self.network.clone(),
+            from,
+            to,
+            amount: amount.map(|amount| format_nanomina(amount, constants::MINA_DECIMALS)),
+            fee: format_nanomina(fee, constants::MINA_DECIMALS),
+            nonce,
+            memo: signed_memo.clone(),
+            valid_until: valid_until.filter(|&slot| slot != u32::MAX),
+            signed,
+            warnings: vec![],
+        };
+        if amount.is_some() && summary.from == summary.to {
+            summary.warn(
+                "selfPayment",
+                "Sender and receiver are the same account".into(),
+            );
+        }
+        if fee >= HIGH_FEE_NANOMINA {
+            summary.warn(
+                "highFee",
+                format!("Fee of {} MINA is unusually high", summary.fee),
+            );
+        }
+        if let Some(amount) = amount.filter(|&amount| fee > amount) {
+            summary.warn(
+                "feeExceedsAmount",
+                format!(
+                    "Fee of {} MINA exceeds the amount of {} MINA",
+                    summary.fee,
+                    format_nanomina(amount, constants::MINA_DECIMALS)
+                ),
+            );
+        }
+        if matches!(&memo, Some(m) if !m.is_empty() && signed_memo.as_ref() != Some(m)) {
+            summary.warn(
+                "memoTruncated",
+                "Memo is longer than 32 bytes and is truncated".into(),
+            );
+        }
+        Ok(summary)
+    }
+}
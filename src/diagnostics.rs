@@ -0,0 +1,182 @@
+use crate::*;
+use mina_signer::PubKey;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[wasm_bindgen(typescript_custom_section)]
+const VERIFICATION_REPORT: &'static str = r#"
+export type VerificationFailureCode =
+    | "malformedInput"
+    | "invalidPublicKey"
+    | "offCurvePoint"
+    | "invalidSignatureField"
+    | "invalidSignatureScalar"
+    | "payloadMismatch"
+    | "wrongNetwork"
+    | "invalidSignature";
+
+export interface VerificationReport {
+    valid: boolean;
+    /** The first step that failed, unset when the signature is valid */
+    failure?: { code: VerificationFailureCode; message: string };
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "VerificationReport")]
+    pub type VerificationReport;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationFailure {
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationReportData {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<VerificationFailure>,
+}
+
+impl From<Result<(), VerificationFailure>> for VerificationReportData {
+    fn from(result: Result<(), VerificationFailure>) -> Self {
+        Self {
+            valid: result.is_ok(),
+            failure: result.err(),
+        }
+    }
+}
+
+fn failure(code: &'static str, message: String) -> VerificationFailure {
+    VerificationFailure { code, message }
+}
+
+fn report(result: Result<(), VerificationFailure>) -> Result<VerificationReport, JsError> {
+    Ok(to_js(&VerificationReportData::from(result))?.unchecked_into())
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Same as `verifyMessage`, but reports which step failed
+    #[cfg(feature = "message")]
+    #[wasm_bindgen(js_name = verifyMessageDetailed)]
+    pub fn verify_message_detailed(
+        &self,
+        signed_message: VerifiableMessage,
+    ) -> Result<VerificationReport, JsError> {
+        report(self.client().diagnose_message(&signed_message))
+    }
+
+    /// Same as `verifyPayment`, but reports which step failed
+    #[cfg(feature = "payment")]
+    #[wasm_bindgen(js_name = verifyPaymentDetailed)]
+    pub fn verify_payment_detailed(
+        &self,
+        signed_payment: VerifiablePayment,
+    ) -> Result<VerificationReport, JsError> {
+        report(self.client().diagnose_payment(&signed_payment))
+    }
+
+    /// Same as `verifyStakeDelegation`, but reports which step failed
+    #[cfg(feature = "delegation")]
+    #[wasm_bindgen(js_name = verifyStakeDelegationDetailed)]
+    pub fn verify_stake_delegation_detailed(
+        &self,
+        signed_stake_delegation: VerifiableStakeDelegation,
+    ) -> Result<VerificationReport, JsError> {
+        report(
+            self.client()
+                .diagnose_stake_delegation(&signed_stake_delegation),
+        )
+    }
+}
+
+impl ClientImpl {
+    #[cfg(feature = "message")]
+    pub fn diagnose_message(&self, signed_message: &JsValue) -> Result<(), VerificationFailure> {
+        let signed: VerifiableMessageData = parse(signed_message)?;
+        let (public_key, message, signature) = signed.parts();
+        let public_key = decode_public_key("publicKey", public_key)?;
+        let signature = decode_signature(signature)?;
+        signed
+            .check_wrapper()
+            .map_err(|e| failure("payloadMismatch", e))?;
+        let message = StringMessage(message.to_string());
+        self.check_signature(|client| {
+            client.verify_message_signature(&signature, &public_key, &message)
+        })
+    }
+
+    #[cfg(feature = "payment")]
+    pub fn diagnose_payment(&self, signed_payment: &JsValue) -> Result<(), VerificationFailure> {
+        let signed: SignedData<PaymentData> = parse(signed_payment)?;
+        let public_key = decode_public_key("from", &signed.data.from)?;
+        decode_public_key("to", &signed.data.to)?;
+        let signature = decode_signature(&signed.signature)?;
+        let payment = MinaPayment::try_from(&signed.data)
+            .map_err(|_| failure("malformedInput", "Invalid payment".into()))?;
+        self.check_signature(|client| {
+            client.verify_payment_signature(&signature, &public_key, &payment)
+        })
+    }
+
+    #[cfg(feature = "delegation")]
+    pub fn diagnose_stake_delegation(
+        &self,
+        signed_stake_delegation: &JsValue,
+    ) -> Result<(), VerificationFailure> {
+        let signed: SignedData<StakeDelegationData> = parse(signed_stake_delegation)?;
+        let public_key = decode_public_key("from", &signed.data.from)?;
+        decode_public_key("to", &signed.data.to)?;
+        let signature = decode_signature(&signed.signature)?;
+        let stake_delegation = MinaStakeDelegation::try_from(&signed.data)
+            .map_err(|_| failure("malformedInput", "Invalid stake delegation".into()))?;
+        self.check_signature(|client| {
+            client.verify_stake_delegation_signature(&signature, &public_key, &stake_delegation)
+        })
+    }
+
+    /// [ClientImpl::check_signature_network] as a verification failure
+    fn check_signature(
+        &self,
+        verify: impl Fn(&ClientImpl) -> bool,
+    ) -> Result<(), VerificationFailure> {
+        self.check_signature_network(verify)
+            .map_err(|other_network| match other_network {
+                Some(other_network) => failure(
+                    "wrongNetwork",
+                    format!(
+                        "Signature is valid on {other_network}, not on {}",
+                        self.network
+                    ),
+                ),
+                None => failure("invalidSignature", "Signature does not verify".into()),
+            })
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: &JsValue) -> Result<T, VerificationFailure> {
+    deserialize_js(value).map_err(|e| failure("malformedInput", e))
+}
+
+fn decode_public_key(field: &str, address: &str) -> Result<PubKey, VerificationFailure> {
+    parse_compressed_public_key(address)
+        .map_err(|e| failure("invalidPublicKey", format!("{field}: {e}")))?;
+    parse_public_key(address).map_err(|_| {
+        failure(
+            "offCurvePoint",
+            format!("{field} {address} is not a point on the curve"),
+        )
+    })
+}
+
+fn decode_signature(signature: &SignatureData) -> Result<MinaSignature, VerificationFailure> {
+    Ok(MinaSignature {
+        rx: signature_field_from_decimal(signature.field.as_str())
+            .map_err(|e| failure("invalidSignatureField", e))?,
+        s: signature_scalar_from_decimal(signature.scalar.as_str())
+            .map_err(|e| failure("invalidSignatureScalar", e))?,
+    })
+}
@@ -1,7 +1,11 @@
 use crate::*;
 use ark_ec::AffineCurve;
+use ark_ff::Zero;
 use mina_curves::pasta::pallas::Affine as CurvePoint;
-use mina_signer::PubKey;
+use mina_signer::{CompressedPubKey, PubKey};
+use serde::{Deserialize, Serialize};
+use std::{ops::Deref, sync::atomic};
+use zeroize::Zeroize;
 
 #[wasm_bindgen(typescript_custom_section)]
 const KEYPAIR: &'static str = r#"
@@ -19,59 +23,289 @@ export interface Keypair {
 extern "C" {
     #[wasm_bindgen(typescript_type = "Keypair")]
     pub type Keypair;
+}
 
-    #[wasm_bindgen(method, getter, js_name = privateKey)]
-    pub fn private_key(this: &Keypair) -> String;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeypairData {
+    pub private_key: String,
+    pub public_key: String,
+}
 
-    #[wasm_bindgen(method, getter, js_name = publicKey)]
-    pub fn public_key(this: &Keypair) -> String;
+impl Drop for KeypairData {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function new_keypair(privateKey, publicKey) {
-            return {
-                privateKey,
-                publicKey,
-            }
-        }
+/// Keypair that wipes its memory on drop, `mina_signer::Keypair` does not
+/// expose its secret scalar mutably, so the whole struct is overwritten
+pub(crate) struct SecretKeypair(MinaKeypair);
 
-        module.exports = {
-            new_keypair,
+impl SecretKeypair {
+    pub(crate) fn from_parts(
+        secret: &<CurvePoint as AffineCurve>::ScalarField,
+        public: CurvePoint,
+    ) -> Self {
+        Self(MinaKeypair::from_parts_unsafe(*secret, public))
+    }
+}
+
+impl From<MinaKeypair> for SecretKeypair {
+    fn from(value: MinaKeypair) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for SecretKeypair {
+    type Target = MinaKeypair;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for SecretKeypair {
+    fn drop(&mut self) {
+        // `MinaKeypair` only holds field elements, so it has no drop glue to run afterwards
+        let ptr = &mut self.0 as *mut MinaKeypair as *mut u8;
+        for i in 0..std::mem::size_of::<MinaKeypair>() {
+            unsafe { ptr.add(i).write_volatile(0) };
         }
-    "#)]
-extern "C" {
-    pub fn new_keypair(private_key: &str, public_key: &str) -> Keypair;
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
 }
 
-impl TryFrom<Keypair> for MinaKeypair {
-    type Error = JsError;
+/// `[version, binprot version, 32 bytes scalar]`
+const PRIVATE_KEY_BYTES: usize = 34;
 
-    fn try_from(value: Keypair) -> Result<Self, Self::Error> {
-        let decoded = bs58::decode(value.private_key())
+/// `[version, binprot versions 1 1, 32 bytes x, is_odd]`
+const PUBLIC_KEY_BYTES: usize = 36;
+
+pub(crate) fn decode_private_key(
+    private_key: &str,
+) -> Result<Zeroizing<<CurvePoint as AffineCurve>::ScalarField>, String> {
+    let decoded = Zeroizing::new(
+        bs58::decode(private_key)
             .with_check(Some(constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE))
             .into_vec()
-            .map_err(map_js_err)?;
-        let private_key_bytes_le = &decoded[2..];
-        let private_key =
-            <CurvePoint as AffineCurve>::ScalarField::from_bytes(private_key_bytes_le)
-                .map_err(map_js_err)?;
-        let public_key = PubKey::from_address(value.public_key().as_str()).map_err(map_js_err)?;
-        Ok(MinaKeypair::from_parts_unsafe(
-            private_key,
+            .map_err(|e| format!("Invalid private key: {e}"))?,
+    );
+    if decoded.len() != PRIVATE_KEY_BYTES {
+        return Err(format!(
+            "Invalid private key: {} bytes, expected {PRIVATE_KEY_BYTES}",
+            decoded.len()
+        ));
+    }
+    if decoded[1] != 1 {
+        return Err("Invalid private key: unsupported version".into());
+    }
+    let private_key_bytes_le = &decoded[2..];
+    let scalar = <CurvePoint as AffineCurve>::ScalarField::from_bytes(private_key_bytes_le)
+        .map(Zeroizing::new)
+        .map_err(|_| "Invalid private key: scalar exceeds the modulus".to_string())?;
+    if scalar.is_zero() {
+        return Err("Invalid private key: scalar is zero".into());
+    }
+    Ok(scalar)
+}
+
+/// Decodes a public key without checking that it is on the curve,
+/// checks the layout before handing it to mina-signer
+pub(crate) fn parse_compressed_public_key(address: &str) -> Result<CompressedPubKey, String> {
+    let decoded = bs58::decode(address)
+        .with_check(Some(constants::PUBLIC_KEY_BASE58_CHECK_VERSION_BYTE))
+        .into_vec()
+        .map_err(|e| format!("Invalid public key: {e}"))?;
+    if decoded.len() != PUBLIC_KEY_BYTES {
+        return Err(format!(
+            "Invalid public key: {} bytes, expected {PUBLIC_KEY_BYTES}",
+            decoded.len()
+        ));
+    }
+    if decoded[1..3] != [1, 1] {
+        return Err("Invalid public key: unsupported version".into());
+    }
+    if decoded[PUBLIC_KEY_BYTES - 1] > 1 {
+        return Err("Invalid public key: parity should be 0 or 1".into());
+    }
+    CompressedPubKey::from_address(address).map_err(|e| format!("Invalid public key: {e}"))
+}
+
+/// Decodes a public key, which has to be on the curve
+pub(crate) fn parse_public_key(address: &str) -> Result<PubKey, String> {
+    parse_compressed_public_key(address)?;
+    PubKey::from_address(address).map_err(|e| format!("Invalid public key: {e}"))
+}
+
+/// Whether `public_key` is a valid base58 public key on the curve, never throws
+#[wasm_bindgen(js_name = isValidPublicKey)]
+pub fn is_valid_public_key(public_key: JsValue) -> bool {
+    matches!(public_key.as_string(), Some(pk) if parse_public_key(&pk).is_ok())
+}
+
+/// Whether `private_key` is a valid base58 private key, never throws
+#[wasm_bindgen(js_name = isValidPrivateKey)]
+pub fn is_valid_private_key(private_key: JsValue) -> bool {
+    matches!(private_key.as_string().map(Zeroizing::new), Some(sk) if decode_private_key(&sk).is_ok())
+}
+
+pub(crate) fn encode_private_key(keypair: &MinaKeypair) -> String {
+    let private_key_bytes_le = Zeroizing::new((*keypair.secret().scalar()).to_bytes());
+    // `with_check_version` copies its input into a buffer it does not wipe,
+    // so the version bytes are prepended here
+    let mut versioned = Zeroizing::new(Vec::with_capacity(private_key_bytes_le.len() + 2));
+    versioned.push(constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE);
+    // binprot version byte
+    versioned.push(1);
+    versioned.extend_from_slice(&private_key_bytes_le);
+    bs58::encode(&versioned[..]).with_check().into_string()
+}
+
+pub(crate) fn keypair_from_private_key(private_key: &str) -> Result<SecretKeypair, String> {
+    let private_key = decode_private_key(private_key)?;
+    MinaKeypair::from_secret(*private_key)
+        .map(SecretKeypair)
+        .map_err(|e| e.to_string())
+}
+
+impl TryFrom<Keypair> for SecretKeypair {
+    type Error = JsError;
+
+    fn try_from(value: Keypair) -> Result<Self, Self::Error> {
+        let value: KeypairData = from_js(&value)?;
+        let private_key = decode_private_key(value.private_key.as_str()).map_err(map_js_err)?;
+        let public_key = parse_public_key(value.public_key.as_str()).map_err(map_js_err)?;
+        Ok(SecretKeypair::from_parts(
+            &private_key,
             public_key.into_point(),
         ))
     }
 }
 
-impl From<MinaKeypair> for Keypair {
-    fn from(value: MinaKeypair) -> Self {
-        let public_key = value.public.into_address();
-        let mut private_key_bytes_le = (*value.secret().scalar()).to_bytes();
-        // binprot version byte
-        private_key_bytes_le.insert(0, 1);
-        let private_key = bs58::encode(private_key_bytes_le)
+impl From<&MinaKeypair> for Keypair {
+    fn from(value: &MinaKeypair) -> Self {
+        KeypairData {
+            private_key: encode_private_key(value),
+            public_key: value.public.into_address(),
+        }
+        .serialize(&JS_SERIALIZER)
+        .expect("KeypairData is always serializable")
+        .unchecked_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Scans the whole wasm linear memory for `!inverted`, the needle is only
+    /// ever held inverted so that the scan itself cannot find a copy of it
+    fn linear_memory_contains(inverted: &[u8]) -> bool {
+        // Allocated before taking the view, growing memory would detach its buffer
+        let mut chunk = vec![0u8; CHUNK_SIZE + inverted.len()];
+        let memory: js_sys::WebAssembly::Memory = wasm_bindgen::memory().unchecked_into();
+        let view = js_sys::Uint8Array::new(&memory.buffer());
+        let len = view.length() as usize;
+        let mut start = 0;
+        while start < len {
+            let end = (start + chunk.len()).min(len);
+            let chunk = &mut chunk[..end - start];
+            view.subarray(start as u32, end as u32).copy_to(chunk);
+            if chunk
+                .windows(inverted.len())
+                .any(|w| w.iter().zip(inverted).all(|(a, b)| *a == !*b))
+            {
+                return true;
+            }
+            start += CHUNK_SIZE;
+        }
+        false
+    }
+
+    #[cfg(feature = "message")]
+    #[wasm_bindgen_test]
+    pub fn decoded_private_key_wiped_after_sign() {
+        let private_key = encode_private_key(&SecretKeypair::from(MinaKeypair::rand(
+            &mut rand::rngs::OsRng,
+        )));
+        let inverted: Vec<u8> = {
+            let scalar = decode_private_key(private_key.as_str()).unwrap();
+            let bytes = Zeroizing::new(scalar.to_bytes());
+            bytes.iter().map(|b| !b).collect()
+        };
+        assert!(!linear_memory_contains(&inverted));
+
+        let keypair = keypair_from_private_key(private_key.as_str()).unwrap();
+        ClientImpl::new("mainnet".into()).sign_message("audit".into(), &keypair);
+        drop(keypair);
+
+        assert!(!linear_memory_contains(&inverted));
+    }
+
+    #[cfg(feature = "keyring")]
+    #[wasm_bindgen_test]
+    pub fn keyring_keys_wiped_after_resize_and_remove() {
+        let private_key = encode_private_key(&SecretKeypair::from(MinaKeypair::rand(
+            &mut rand::rngs::OsRng,
+        )));
+        let inverted: Vec<u8> = {
+            let scalar = decode_private_key(private_key.as_str()).unwrap();
+            let bytes = Zeroizing::new(scalar.to_bytes());
+            bytes.iter().map(|b| !b).collect()
+        };
+        assert!(!linear_memory_contains(&inverted));
+
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &"network".into(), &"mainnet".into()).unwrap();
+        let mut keyring = Keyring::new(options.unchecked_ref()).unwrap();
+        let imported = keyring.import_key(private_key).unwrap();
+        // Enough keys for the map to be reallocated several times
+        let generated: Vec<_> = (0..100).map(|_| keyring.generate_key()).collect();
+        let key_id = |entry: &KeyringEntry| {
+            js_sys::Reflect::get(entry, &"keyId".into())
+                .unwrap()
+                .as_string()
+                .unwrap()
+        };
+        assert!(keyring.remove_key(&key_id(&imported)));
+        for entry in &generated {
+            assert!(keyring.remove_key(&key_id(entry)));
+        }
+
+        assert!(!linear_memory_contains(&inverted));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn key_decoding_never_panics() {
+        let keypair = SecretKeypair::from(MinaKeypair::rand(&mut rand::rngs::OsRng));
+        let private_key = encode_private_key(&keypair);
+        let public_key = keypair.public.into_address();
+        assert!(is_valid_private_key(private_key.as_str().into()));
+        assert!(is_valid_public_key(public_key.as_str().into()));
+        assert!(!is_valid_private_key(public_key.as_str().into()));
+        assert!(!is_valid_public_key(private_key.as_str().into()));
+        assert!(!is_valid_public_key(JsValue::from(1)));
+
+        // Valid checksums around too short or too long payloads
+        for len in [0, 1, 2, 3, 33, 35, 37] {
+            let payload = vec![1u8; len];
+            let short_private_key = bs58::encode(&payload)
+                .with_check_version(constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE)
+                .into_string();
+            assert!(decode_private_key(&short_private_key).is_err());
+            let short_public_key = bs58::encode(&payload)
+                .with_check_version(constants::PUBLIC_KEY_BASE58_CHECK_VERSION_BYTE)
+                .into_string();
+            assert!(parse_public_key(&short_public_key).is_err());
+        }
+        // scalar of all ones exceeds the modulus
+        let out_of_range = bs58::encode([[1u8].as_slice(), &[0xff; 32]].concat())
             .with_check_version(constants::PRIVATE_KEY_BASE58_CHECK_VERSION_BYTE)
             .into_string();
-        new_keypair(private_key.as_str(), public_key.as_str())
+        assert!(decode_private_key(&out_of_range).is_err());
     }
 }
@@ -0,0 +1,188 @@
+use crate::*;
+use ark_ec::AffineCurve;
+use mina_curves::pasta::pallas::Affine as CurvePoint;
+use mina_signer::PubKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[wasm_bindgen(typescript_custom_section)]
+const KEYRING: &'static str = r#"
+export type KeyId = string;
+
+export interface KeyringEntry {
+    keyId: KeyId;
+    publicKey: PublicKey;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "KeyringEntry")]
+    pub type KeyringEntry;
+
+    #[wasm_bindgen(typescript_type = "KeyringEntry[]")]
+    pub type KeyringEntryArray;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyringEntryData {
+    pub key_id: String,
+    pub public_key: String,
+}
+
+/// Private key held in wasm memory, the scalar is wiped on drop, it is boxed so that
+/// growing the key map only moves the pointer and leaves no copies of it behind
+struct KeyringKey {
+    secret: Zeroizing<Box<<CurvePoint as AffineCurve>::ScalarField>>,
+    public: PubKey,
+}
+
+impl KeyringKey {
+    fn keypair(&self) -> SecretKeypair {
+        SecretKeypair::from_parts(&self.secret, self.public.clone().into_point())
+    }
+}
+
+/// Keeps private keys inside wasm memory, js only sees opaque key ids and public keys,
+/// private keys leave wasm only through an explicit `exportKey` call
+#[wasm_bindgen]
+pub struct Keyring {
+    client: ClientImpl,
+    keys: HashMap<String, KeyringKey>,
+}
+
+impl Keyring {
+    fn key(&self, key_id: &str) -> Result<&KeyringKey, JsError> {
+        self.keys
+            .get(key_id)
+            .ok_or_else(|| JsError::new(&format!("Unknown key id: {key_id}")))
+    }
+
+    fn insert(&mut self, keypair: &MinaKeypair) -> KeyringEntry {
+        let mut key_id = [0; 16];
+        rand::rngs::OsRng.fill_bytes(&mut key_id);
+        let entry = KeyringEntryData {
+            key_id: hex::encode(key_id),
+            public_key: keypair.public.into_address(),
+        };
+        self.keys.insert(
+            entry.key_id.clone(),
+            KeyringKey {
+                secret: Zeroizing::new(Box::new(*keypair.secret().scalar())),
+                public: keypair.public.clone(),
+            },
+        );
+        to_js(&entry)
+            .expect("KeyringEntryData is always serializable")
+            .unchecked_into()
+    }
+}
+
+#[wasm_bindgen]
+impl Keyring {
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: &ClientOptions) -> Result<Keyring, JsError> {
+        Ok(Keyring {
+            client: ClientImpl::from_options(options)?,
+            keys: HashMap::new(),
+        })
+    }
+
+    /// Generates a new key inside the keyring
+    #[wasm_bindgen(js_name = generateKey)]
+    pub fn generate_key(&mut self) -> KeyringEntry {
+        let keypair = SecretKeypair::from(MinaKeypair::rand(&mut rand::rngs::OsRng));
+        self.insert(&keypair)
+    }
+
+    /// Imports a base58 encoded private key
+    #[wasm_bindgen(js_name = importKey)]
+    pub fn import_key(&mut self, private_key: String) -> Result<KeyringEntry, JsError> {
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
+        Ok(self.insert(&keypair))
+    }
+
+    /// Exports the base58 encoded private key, this is the only way a key leaves the keyring
+    #[wasm_bindgen(js_name = exportKey)]
+    pub fn export_key(&self, key_id: &str) -> Result<String, JsError> {
+        Ok(encode_private_key(&self.key(key_id)?.keypair()))
+    }
+
+    /// Removes a key and wipes its private key, returns false if the key id is unknown
+    #[wasm_bindgen(js_name = removeKey)]
+    pub fn remove_key(&mut self, key_id: &str) -> bool {
+        self.keys.remove(key_id).is_some()
+    }
+
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self, key_id: &str) -> Result<String, JsError> {
+        Ok(self.key(key_id)?.public.into_address())
+    }
+
+    pub fn keys(&self) -> Result<KeyringEntryArray, JsError> {
+        let entries: Vec<_> = self
+            .keys
+            .iter()
+            .map(|(key_id, key)| KeyringEntryData {
+                key_id: key_id.clone(),
+                public_key: key.public.into_address(),
+            })
+            .collect();
+        Ok(to_js(&entries)?.unchecked_into())
+    }
+
+    #[cfg(feature = "message")]
+    #[wasm_bindgen(js_name = signMessage)]
+    pub fn sign_message(&self, message: String, key_id: &str) -> Result<SignedMessage, JsError> {
+        let key = self.key(key_id)?;
+        let signature = self.client.sign_message(message.clone(), &key.keypair());
+        let signed_message = SignedMessageData::new(
+            signature,
+            MessageData {
+                public_key: key.public.into_address(),
+                message,
+            },
+        );
+        Ok(to_js(&signed_message)?.unchecked_into())
+    }
+
+    #[cfg(feature = "payment")]
+    #[wasm_bindgen(js_name = signPayment)]
+    pub fn sign_payment(&self, payment: Payment, key_id: &str) -> Result<SignedPayment, JsError> {
+        let key = self.key(key_id)?;
+        let payment: Payment = self.client.resolve_valid_until(&payment)?.unchecked_into();
+        let data = payment.to_data()?;
+        self.client.check_payment_data(&data).map_err(map_js_err)?;
+        let signature = self
+            .client
+            .sign_payment(&(&data).try_into()?, &key.keypair())
+            .map_err(map_js_err)?;
+        new_signed(signature, &payment)
+    }
+
+    #[cfg(feature = "delegation")]
+    #[wasm_bindgen(js_name = signStakeDelegation)]
+    pub fn sign_stake_delegation(
+        &self,
+        stake_delegation: StakeDelegation,
+        key_id: &str,
+    ) -> Result<SignedStakeDelegation, JsError> {
+        let key = self.key(key_id)?;
+        let stake_delegation: StakeDelegation = self
+            .client
+            .resolve_valid_until(&stake_delegation)?
+            .unchecked_into();
+        let data = stake_delegation.to_data()?;
+        self.client
+            .check_stake_delegation_data(&data)
+            .map_err(map_js_err)?;
+        let signature = self
+            .client
+            .sign_stake_delegation(&(&data).try_into()?, &key.keypair())
+            .map_err(map_js_err)?;
+        new_signed(signature, &stake_delegation)
+    }
+}
@@ -0,0 +1,591 @@
+use crate::*;
+use mina_signer::PubKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[wasm_bindgen(typescript_custom_section)]
+const LEDGER_SIMULATION: &'static str = r#"
+/** An account of a genesis ledger, amounts are in MINA, e.g. "1000" or "0.5" */
+export interface LedgerAccount {
+    pk: PublicKey;
+    balance: MinaAmount | number;
+    nonce?: UInt32;
+    /** The account itself when unset */
+    delegate?: PublicKey;
+    timing?: {
+        initial_minimum_balance: MinaAmount | number;
+        cliff_time: UInt32;
+        cliff_amount: MinaAmount | number;
+        vesting_period: UInt32;
+        vesting_increment: MinaAmount | number;
+    };
+}
+
+/** The `ledger` of a daemon runtime config, or the runtime config itself */
+export type LedgerSnapshot = { accounts: LedgerAccount[] } | { ledger: { accounts: LedgerAccount[] } };
+
+export type LedgerRejectionCode =
+    | "invalidSignature"
+    | "expired"
+    | "unknownAccount"
+    | "nonceGap"
+    | "staleNonce"
+    | "insufficientFunds"
+    | "lockedBalance"
+    | "amountBelowAccountCreationFee"
+    | "unknownDelegate"
+    | "balanceOverflow"
+    | "nonceOverflow";
+
+export interface LedgerTransactionResult {
+    index: number;
+    kind: "payment" | "stakeDelegation";
+    /** Set when the fee was charged and the nonce bumped, even if the transaction then failed */
+    applied: boolean;
+    /**
+     * Why the transaction was rejected: a bad signature, an expired `validUntil`, a wrong nonce
+     * or a fee the sender cannot pay. Rejected transactions leave the ledger untouched
+     */
+    rejection?: { code: LedgerRejectionCode; message: string };
+    /**
+     * Why an applied transaction failed, e.g. an amount the sender cannot spend or an unknown
+     * delegate. Only the fee and the nonce of the sender changed, as with the daemon
+     */
+    failure?: { code: LedgerRejectionCode; message: string };
+}
+
+export interface LedgerAccountChange {
+    publicKey: PublicKey;
+    /** Set when the account was created by a payment */
+    created: boolean;
+    /** MINA with 9 decimals, unset for created accounts */
+    balanceBefore?: string;
+    /** MINA with 9 decimals */
+    balanceAfter: string;
+    nonceBefore?: number;
+    nonceAfter: number;
+    delegateBefore?: PublicKey;
+    delegateAfter: PublicKey;
+}
+
+export interface LedgerSimulation {
+    globalSlot: number;
+    /** In the order the transactions were given */
+    transactions: LedgerTransactionResult[];
+    /** Accounts whose balance, nonce or delegate changed, in ledger order */
+    changes: LedgerAccountChange[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "LedgerSnapshot | string")]
+    pub type LedgerSnapshot;
+
+    #[wasm_bindgen(
+        typescript_type = "(Signed<Payment, SignatureLike> | Signed<StakeDelegation, SignatureLike>)[]"
+    )]
+    pub type VerifiableTransactionArray;
+
+    #[wasm_bindgen(typescript_type = "LedgerSimulation")]
+    pub type LedgerSimulation;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LedgerSnapshotData {
+    #[serde(default)]
+    pub ledger: Option<LedgerAccountsData>,
+    #[serde(default)]
+    pub accounts: Option<Vec<LedgerAccountData>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LedgerAccountsData {
+    pub accounts: Vec<LedgerAccountData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LedgerAccountData {
+    pub pk: String,
+    #[serde(with = "mina_amount")]
+    pub balance: u64,
+    #[serde(default, with = "option_uint")]
+    pub nonce: Option<u32>,
+    #[serde(default)]
+    pub delegate: Option<String>,
+    #[serde(default)]
+    pub timing: Option<LedgerTimingData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LedgerTimingData {
+    #[serde(with = "mina_amount")]
+    pub initial_minimum_balance: u64,
+    #[serde(with = "uint")]
+    pub cliff_time: u32,
+    #[serde(with = "mina_amount")]
+    pub cliff_amount: u64,
+    #[serde(with = "uint")]
+    pub vesting_period: u32,
+    #[serde(with = "mina_amount")]
+    pub vesting_increment: u64,
+}
+
+impl From<&LedgerTimingData> for AccountTimingData {
+    fn from(v: &LedgerTimingData) -> Self {
+        Self {
+            initial_minimum_balance: v.initial_minimum_balance,
+            cliff_time: v.cliff_time,
+            cliff_amount: v.cliff_amount,
+            vesting_period: v.vesting_period,
+            vesting_increment: v.vesting_increment,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerAccountState {
+    pub balance: u64,
+    pub nonce: u32,
+    pub delegate: String,
+    pub timing: Option<AccountTimingData>,
+}
+
+/// Accounts in ledger order, with their state before the simulation
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedLedger {
+    accounts: Vec<(String, Option<LedgerAccountState>, LedgerAccountState)>,
+    index: HashMap<String, usize>,
+}
+
+impl SimulatedLedger {
+    pub fn new(snapshot: &LedgerSnapshotData) -> Result<Self, String> {
+        let accounts = match (&snapshot.ledger, &snapshot.accounts) {
+            (Some(ledger), _) => &ledger.accounts,
+            (None, Some(accounts)) => accounts,
+            (None, None) => return Err("Invalid ledger: missing accounts".into()),
+        };
+        let mut ledger = Self::default();
+        for (i, account) in accounts.iter().enumerate() {
+            parse_compressed_public_key(account.pk.as_str())
+                .map_err(|e| format!("Invalid accounts[{i}].pk: {e}"))?;
+            if ledger.index.contains_key(&account.pk) {
+                return Err(format!(
+                    "Invalid accounts[{i}]: duplicate account {}",
+                    account.pk
+                ));
+            }
+            let state = LedgerAccountState {
+                balance: account.balance,
+                nonce: account.nonce.unwrap_or(0),
+                delegate: account
+                    .delegate
+                    .clone()
+                    .unwrap_or_else(|| account.pk.clone()),
+                timing: account.timing.as_ref().map(AccountTimingData::from),
+            };
+            ledger.insert(account.pk.clone(), Some(state.clone()), state);
+        }
+        Ok(ledger)
+    }
+
+    fn insert(
+        &mut self,
+        pk: String,
+        before: Option<LedgerAccountState>,
+        after: LedgerAccountState,
+    ) {
+        self.index.insert(pk.clone(), self.accounts.len());
+        self.accounts.push((pk, before, after));
+    }
+
+    pub fn get(&self, pk: &str) -> Option<&LedgerAccountState> {
+        self.index.get(pk).map(|&i| &self.accounts[i].2)
+    }
+
+    fn get_mut(&mut self, pk: &str) -> Option<&mut LedgerAccountState> {
+        self.index.get(pk).map(|&i| &mut self.accounts[i].2)
+    }
+
+    pub fn changes(&self) -> Vec<LedgerAccountChangeData> {
+        self.accounts
+            .iter()
+            .filter(|(_, before, after)| before.as_ref() != Some(after))
+            .map(|(pk, before, after)| LedgerAccountChangeData {
+                public_key: pk.clone(),
+                created: before.is_none(),
+                balance_before: before
+                    .as_ref()
+                    .map(|b| format_nanomina(b.balance, constants::MINA_DECIMALS)),
+                balance_after: format_nanomina(after.balance, constants::MINA_DECIMALS),
+                nonce_before: before.as_ref().map(|b| b.nonce),
+                nonce_after: after.nonce,
+                delegate_before: before.as_ref().map(|b| b.delegate.clone()),
+                delegate_after: after.delegate.clone(),
+            })
+            .collect()
+    }
+
+    /// Applies a payment, `Err` when the fee payer checks reject it and the ledger is untouched,
+    /// `Ok(Some(failure))` when the fee was charged and the nonce bumped but the transfer failed
+    fn apply_payment(
+        &mut self,
+        payment: &PaymentData,
+        global_slot: u32,
+    ) -> Result<Option<LedgerRejection>, LedgerRejection> {
+        self.charge_fee_payer(&payment.from, payment.nonce, payment.fee, global_slot)?;
+        Ok(self.transfer(payment, global_slot).err())
+    }
+
+    /// Moves the amount of a payment whose fee has been charged
+    fn transfer(&mut self, payment: &PaymentData, global_slot: u32) -> Result<(), LedgerRejection> {
+        let sender = self.get(&payment.from).expect("charged");
+        let sender_balance = spend(sender, payment.amount, global_slot, "amount")?;
+        if payment.from == payment.to {
+            return Ok(());
+        }
+        match self.get(&payment.to) {
+            Some(receiver) => {
+                let balance = receiver
+                    .balance
+                    .checked_add(payment.amount)
+                    .ok_or_else(|| {
+                        rejection(
+                            "balanceOverflow",
+                            format!("Balance of {} would exceed the u64 range", payment.to),
+                        )
+                    })?;
+                self.get_mut(&payment.to).expect("checked").balance = balance;
+            }
+            None => {
+                let balance = payment
+                    .amount
+                    .checked_sub(constants::ACCOUNT_CREATION_FEE)
+                    .ok_or_else(|| {
+                        let fee = format_nanomina(
+                            constants::ACCOUNT_CREATION_FEE,
+                            constants::MINA_DECIMALS,
+                        );
+                        rejection(
+                            "amountBelowAccountCreationFee",
+                            format!(
+                                "{} is a new account, the amount has to cover its {fee} MINA creation fee",
+                                payment.to
+                            ),
+                        )
+                    })?;
+                let state = LedgerAccountState {
+                    balance,
+                    nonce: 0,
+                    delegate: payment.to.clone(),
+                    timing: None,
+                };
+                self.insert(payment.to.clone(), None, state);
+            }
+        }
+        self.get_mut(&payment.from).expect("charged").balance = sender_balance;
+        Ok(())
+    }
+
+    /// Same as [Self::apply_payment], the delegate has to be in the ledger
+    fn apply_stake_delegation(
+        &mut self,
+        stake_delegation: &StakeDelegationData,
+        global_slot: u32,
+    ) -> Result<Option<LedgerRejection>, LedgerRejection> {
+        self.charge_fee_payer(
+            &stake_delegation.from,
+            stake_delegation.nonce,
+            stake_delegation.fee,
+            global_slot,
+        )?;
+        if self.get(&stake_delegation.to).is_none() {
+            return Ok(Some(rejection(
+                "unknownDelegate",
+                format!("Delegate {} is not in the ledger", stake_delegation.to),
+            )));
+        }
+        self.get_mut(&stake_delegation.from)
+            .expect("charged")
+            .delegate = stake_delegation.to.clone();
+        Ok(None)
+    }
+
+    /// Checks that the fee payer exists, has the expected nonce and can pay `fee`,
+    /// then charges the fee and increments the nonce, as the daemon does for failed commands too
+    fn charge_fee_payer(
+        &mut self,
+        from: &str,
+        nonce: u32,
+        fee: u64,
+        global_slot: u32,
+    ) -> Result<(), LedgerRejection> {
+        let account = self.get(from).ok_or_else(|| {
+            rejection(
+                "unknownAccount",
+                format!("Sender {from} is not in the ledger"),
+            )
+        })?;
+        if nonce > account.nonce {
+            return Err(rejection(
+                "nonceGap",
+                format!(
+                    "nonce {nonce} is ahead of the account nonce {}",
+                    account.nonce
+                ),
+            ));
+        }
+        if nonce < account.nonce {
+            return Err(rejection(
+                "staleNonce",
+                format!(
+                    "nonce {nonce} is behind the account nonce {}",
+                    account.nonce
+                ),
+            ));
+        }
+        let next_nonce = account.nonce.checked_add(1).ok_or_else(|| {
+            rejection(
+                "nonceOverflow",
+                format!("nonce {nonce} of {from} cannot be incremented"),
+            )
+        })?;
+        let balance = spend(account, fee, global_slot, "fee")?;
+        let account = self.get_mut(from).expect("checked");
+        account.balance = balance;
+        account.nonce = next_nonce;
+        Ok(())
+    }
+}
+
+/// The balance of `account` once `amount` is spent, which has to keep its locked balance
+fn spend(
+    account: &LedgerAccountState,
+    amount: u64,
+    global_slot: u32,
+    what: &str,
+) -> Result<u64, LedgerRejection> {
+    let remaining = account.balance.checked_sub(amount).ok_or_else(|| {
+        rejection(
+            "insufficientFunds",
+            format!(
+                "balance of {} MINA does not cover the {what}",
+                format_nanomina(account.balance, constants::MINA_DECIMALS)
+            ),
+        )
+    })?;
+    if let Some(timing) = &account.timing {
+        let locked = timing.locked_at(global_slot);
+        if remaining < locked {
+            return Err(rejection(
+                "lockedBalance",
+                format!(
+                    "{} MINA are still locked at slot {global_slot}, only {} MINA can be spent",
+                    format_nanomina(locked, constants::MINA_DECIMALS),
+                    format_nanomina(
+                        account.balance.saturating_sub(locked),
+                        constants::MINA_DECIMALS
+                    )
+                ),
+            ));
+        }
+    }
+    Ok(remaining)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LedgerRejection {
+    pub code: &'static str,
+    pub message: String,
+}
+
+fn rejection(code: &'static str, message: String) -> LedgerRejection {
+    LedgerRejection { code, message }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerTransactionResultData {
+    pub index: usize,
+    pub kind: &'static str,
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<LedgerRejection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<LedgerRejection>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerAccountChangeData {
+    pub public_key: String,
+    pub created: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_before: Option<String>,
+    pub balance_after: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce_before: Option<u32>,
+    pub nonce_after: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegate_before: Option<String>,
+    pub delegate_after: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSimulationData {
+    pub global_slot: u32,
+    pub transactions: Vec<LedgerTransactionResultData>,
+    pub changes: Vec<LedgerAccountChangeData>,
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Applies signed payments and stake delegations in order to a ledger snapshot without a node,
+    /// `globalSlot` defaults to the current slot of the client network
+    #[wasm_bindgen(js_name = simulateLedger)]
+    pub fn simulate_ledger(
+        &self,
+        ledger: LedgerSnapshot,
+        transactions: VerifiableTransactionArray,
+        global_slot: Option<u32>,
+    ) -> Result<LedgerSimulation, JsError> {
+        let snapshot: LedgerSnapshotData = match ledger.as_string() {
+            Some(json) => from_json(json.as_str()),
+            None => deserialize_js(&ledger),
+        }
+        .map_err(|e| JsError::new(&format!("Invalid ledger: {e}")))?;
+        let mut ledger = SimulatedLedger::new(&snapshot).map_err(map_js_err)?;
+        let transactions = js_sys::Array::from(&transactions)
+            .iter()
+            .enumerate()
+            .map(|(i, transaction)| {
+                let transaction = self.client().resolve_transaction_valid_until(&transaction)?;
+                deserialize_js(&transaction).map_err(|_| {
+                    JsError::new(&format!(
+                        "Invalid transaction at index {i}, expected a signed payment or stake delegation"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<SignedData<UnsignedTransactionData>>, _>>()?;
+        let global_slot = match global_slot {
+            Some(slot) => slot,
+            None => self
+                .client()
+                .slot_time
+                .slot_at(js_sys::Date::now())
+                .map_err(map_js_err)?,
+        };
+        let simulation = self
+            .client()
+            .simulate_ledger(&mut ledger, &transactions, global_slot);
+        Ok(to_js(&simulation)?.unchecked_into())
+    }
+}
+
+impl ClientImpl {
+    pub fn simulate_ledger(
+        &self,
+        ledger: &mut SimulatedLedger,
+        transactions: &[SignedData<UnsignedTransactionData>],
+        global_slot: u32,
+    ) -> LedgerSimulationData {
+        let transactions = transactions
+            .iter()
+            .enumerate()
+            .map(|(index, signed)| {
+                let (kind, result) = match &signed.data {
+                    UnsignedTransactionData::Payment(payment) => (
+                        "payment",
+                        self.check_payment_signature(&signed.signature, payment)
+                            .and_then(|_| check_valid_until(payment.valid_until, global_slot))
+                            .and_then(|_| ledger.apply_payment(payment, global_slot)),
+                    ),
+                    UnsignedTransactionData::StakeDelegation(stake_delegation) => (
+                        "stakeDelegation",
+                        self.check_stake_delegation_signature(&signed.signature, stake_delegation)
+                            .and_then(|_| {
+                                check_valid_until(stake_delegation.valid_until, global_slot)
+                            })
+                            .and_then(|_| {
+                                ledger.apply_stake_delegation(stake_delegation, global_slot)
+                            }),
+                    ),
+                };
+                let (rejection, failure) = match result {
+                    Ok(failure) => (None, failure),
+                    Err(rejection) => (Some(rejection), None),
+                };
+                LedgerTransactionResultData {
+                    index,
+                    kind,
+                    applied: rejection.is_none(),
+                    rejection,
+                    failure,
+                }
+            })
+            .collect();
+        LedgerSimulationData {
+            global_slot,
+            transactions,
+            changes: ledger.changes(),
+        }
+    }
+
+    fn check_payment_signature(
+        &self,
+        signature: &SignatureData,
+        payment: &PaymentData,
+    ) -> Result<(), LedgerRejection> {
+        let (signature, public_key) = decode_signer(signature, &payment.from)?;
+        let payment = MinaPayment::try_from(payment)
+            .map_err(|_| rejection("invalidSignature", "Invalid payment".into()))?;
+        if self.verify_payment_signature(&signature, &public_key, &payment) {
+            Ok(())
+        } else {
+            Err(rejection(
+                "invalidSignature",
+                "Signature does not verify".into(),
+            ))
+        }
+    }
+
+    fn check_stake_delegation_signature(
+        &self,
+        signature: &SignatureData,
+        stake_delegation: &StakeDelegationData,
+    ) -> Result<(), LedgerRejection> {
+        let (signature, public_key) = decode_signer(signature, &stake_delegation.from)?;
+        let stake_delegation = MinaStakeDelegation::try_from(stake_delegation)
+            .map_err(|_| rejection("invalidSignature", "Invalid stake delegation".into()))?;
+        if self.verify_stake_delegation_signature(&signature, &public_key, &stake_delegation) {
+            Ok(())
+        } else {
+            Err(rejection(
+                "invalidSignature",
+                "Signature does not verify".into(),
+            ))
+        }
+    }
+}
+
+fn decode_signer(
+    signature: &SignatureData,
+    from: &str,
+) -> Result<(MinaSignature, PubKey), LedgerRejection> {
+    let signature = MinaSignature::try_from(signature)
+        .map_err(|_| rejection("invalidSignature", "Signature is malformed".into()))?;
+    let public_key =
+        parse_public_key(from).map_err(|e| rejection("invalidSignature", format!("from: {e}")))?;
+    Ok((signature, public_key))
+}
+
+fn check_valid_until(valid_until: Option<u32>, global_slot: u32) -> Result<(), LedgerRejection> {
+    match valid_until {
+        Some(valid_until) if valid_until < global_slot => Err(rejection(
+            "expired",
+            format!("validUntil {valid_until} is before slot {global_slot}"),
+        )),
+        _ => Ok(()),
+    }
+}
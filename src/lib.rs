@@ -6,23 +6,109 @@ pub use client::*;
 mod keypair;
 pub use keypair::*;
 
+#[cfg(feature = "message")]
 mod message;
+#[cfg(feature = "message")]
 pub use message::*;
 
+#[cfg(feature = "payment")]
 mod payment;
+#[cfg(feature = "payment")]
 pub use payment::*;
 
+#[cfg(feature = "delegation")]
 mod stake_delegation;
+#[cfg(feature = "delegation")]
 pub use stake_delegation::*;
 
 mod signature;
 pub use signature::*;
 
+mod currency;
+pub use currency::*;
+
+mod slot;
+pub use slot::*;
+
+mod timing;
+pub use timing::*;
+
+mod public_key;
+pub use public_key::*;
+
+mod codec;
+pub use codec::*;
+
+#[cfg(feature = "keyring")]
+mod keyring;
+#[cfg(feature = "keyring")]
+pub use keyring::*;
+
+#[cfg(feature = "rosetta")]
 mod rosetta;
+#[cfg(feature = "rosetta")]
 use rosetta::*;
 
+#[cfg(any(feature = "rosetta", feature = "audit"))]
+mod signed_command;
+#[cfg(any(feature = "rosetta", feature = "audit"))]
+use signed_command::*;
+
 mod constants;
 
+#[cfg(feature = "component")]
+mod component;
+
+#[cfg(all(feature = "payment", feature = "delegation"))]
+mod transaction;
+#[cfg(all(feature = "payment", feature = "delegation"))]
+pub use transaction::*;
+
+#[cfg(feature = "describe")]
+mod describe;
+#[cfg(feature = "describe")]
+pub use describe::*;
+
+#[cfg(feature = "policy")]
+mod policy;
+#[cfg(feature = "policy")]
+pub use policy::*;
+
+#[cfg(feature = "validate")]
+mod validate;
+#[cfg(feature = "validate")]
+pub use validate::*;
+
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::*;
+
+#[cfg(feature = "batch")]
+mod batch;
+#[cfg(feature = "batch")]
+pub use batch::*;
+
+#[cfg(feature = "receipt")]
+mod receipt;
+#[cfg(feature = "receipt")]
+pub use receipt::*;
+
+#[cfg(feature = "ledger")]
+mod ledger;
+#[cfg(feature = "ledger")]
+pub use ledger::*;
+
+#[cfg(feature = "audit")]
+mod audit;
+#[cfg(feature = "audit")]
+pub use audit::*;
+
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+
 mod utils;
 use utils::*;
 
@@ -32,3 +118,4 @@ use ark_ec::AffineCurve;
 use mina_curves::pasta::pallas::Affine as CurvePoint;
 use mina_signer::schnorr::Message as SchnorrMessage;
 use o1_utils::FieldHelpers;
+use zeroize::Zeroizing;
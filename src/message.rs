@@ -2,6 +2,7 @@ use crate::*;
 use derive_more::{From, Into};
 use mina_hasher::{Hashable, ROInput};
 use mina_signer::NetworkId;
+use serde::{Deserialize, Serialize};
 
 #[wasm_bindgen(typescript_custom_section)]
 const MESSAGE: &'static str = r#"
@@ -9,55 +10,121 @@ export interface Message {
     publicKey: PublicKey;
     message: string;
 }
+
+/** `signature.string` and `signature.signer` repeat `data` */
+export interface SignedMessage<S = Signature> {
+    signature: { string: string; signer: PublicKey; signature: S };
+    data: Message;
+}
+
+/** Signed message in the shape returned by the Auro wallet */
+export interface AuroSignedMessage {
+    publicKey: PublicKey;
+    data: string;
+    signature: SignatureLike;
+}
 "#;
 
 #[wasm_bindgen]
 extern "C" {
     pub type Message;
 
-    #[wasm_bindgen(method, getter, js_name = publicKey)]
-    pub fn public_key(this: &Message) -> String;
+    #[wasm_bindgen(typescript_type = "SignedMessage")]
+    pub type SignedMessage;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn message(this: &Message) -> String;
+    #[wasm_bindgen(typescript_type = "SignedMessage<SignatureLike> | AuroSignedMessage")]
+    pub type VerifiableMessage;
+}
 
-    #[wasm_bindgen(typescript_type = "Signed<Message>")]
-    pub type SignedMessage;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageData {
+    pub public_key: String,
+    pub message: String,
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn signature(this: &SignedMessage) -> SignatureWrapper;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureWrapperData {
+    pub string: String,
+    pub signer: String,
+    pub signature: SignatureData,
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn data(this: &SignedMessage) -> Message;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedMessageData {
+    pub signature: SignatureWrapperData,
+    pub data: MessageData,
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function new_message(publicKey, message) {
-            return {
-                publicKey,
-                message,
-            }
+impl SignedMessageData {
+    pub fn new(signature: MinaSignature, data: MessageData) -> Self {
+        Self {
+            signature: SignatureWrapperData {
+                string: data.message.clone(),
+                signer: data.public_key.clone(),
+                signature: signature.into(),
+            },
+            data,
         }
+    }
+}
 
-        function new_signed_message(signature, data) {
-            return {
-                signature: {
-                    string: data.message,
-                    signer: data.publicKey,
-                    signature,
-                },
-                data,
-            }
+impl SignedMessage {
+    pub fn to_data(&self) -> Result<SignedMessageData, JsError> {
+        from_js(self)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuroSignedMessageData {
+    pub public_key: String,
+    pub data: String,
+    pub signature: SignatureData,
+}
+
+/// Either of the signed message shapes accepted by `verifyMessage`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum VerifiableMessageData {
+    Signed(SignedMessageData),
+    Auro(AuroSignedMessageData),
+}
+
+impl VerifiableMessageData {
+    /// The signed `(publicKey, message, signature)`
+    pub fn parts(&self) -> (&str, &str, &SignatureData) {
+        match self {
+            Self::Signed(v) => (&v.data.public_key, &v.data.message, &v.signature.signature),
+            Self::Auro(v) => (&v.public_key, &v.data, &v.signature),
         }
+    }
 
-        module.exports = {
-            new_message, new_signed_message
+    /// Checks that `signature.signer` and `signature.string` match `data`,
+    /// the Auro shape has no such duplicated fields
+    pub fn check_wrapper(&self) -> Result<(), String> {
+        let Self::Signed(v) = self else {
+            return Ok(());
+        };
+        if v.signature.signer != v.data.public_key {
+            Err(format!(
+                "signature.signer {} differs from data.publicKey {}",
+                v.signature.signer, v.data.public_key
+            ))
+        } else if v.signature.string != v.data.message {
+            Err("signature.string differs from data.message".into())
+        } else {
+            Ok(())
         }
-    "#)]
-extern "C" {
-    pub fn new_message(public_key: &str, message: &str) -> Message;
+    }
+}
 
-    pub fn new_signed_message(signature: Signature, message: Message) -> SignedMessage;
+impl VerifiableMessage {
+    pub fn to_data(&self) -> Result<VerifiableMessageData, JsError> {
+        from_js(self).map_err(|_| {
+            JsError::new("Expected a SignedMessage or an Auro style { publicKey, data, signature }")
+        })
+    }
 }
 
 #[derive(Debug, Clone, From, Into)]
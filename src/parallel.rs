@@ -0,0 +1,144 @@
+use crate::*;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex,
+    },
+};
+
+/// Threads of the pool waiting for their worker, taken once by `startWorker`
+static PENDING_THREADS: Lazy<Mutex<HashMap<u32, rayon::ThreadBuilder>>> =
+    Lazy::new(Default::default);
+static NEXT_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+/// Set once `initThreadPool` built the global pool
+static THREAD_POOL_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether `initThreadPool` succeeded, without it using rayon panics on targets
+/// that cannot spawn threads
+pub(crate) fn thread_pool_started() -> bool {
+    THREAD_POOL_STARTED.load(Ordering::Acquire)
+}
+
+/// Builds the global rayon thread pool, `spawn(module, memory, thread)` is called
+/// once per thread and is expected to start a worker that instantiates `module` with the
+/// shared `memory` and then calls `startWorker(thread)`
+#[wasm_bindgen(js_name = initThreadPool)]
+pub fn init_thread_pool(num_threads: usize, spawn: &js_sys::Function) -> Result<(), JsError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .spawn_handler(|thread| {
+            let id = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+            PENDING_THREADS.lock().unwrap().insert(id, thread);
+            spawn
+                .call3(
+                    &JsValue::NULL,
+                    &wasm_bindgen::module(),
+                    &wasm_bindgen::memory(),
+                    &id.into(),
+                )
+                .map(|_| ())
+                .map_err(|e| {
+                    PENDING_THREADS.lock().unwrap().remove(&id);
+                    std::io::Error::other(format!("{e:?}"))
+                })
+        })
+        .build_global()
+        .map_err(map_js_err)?;
+    THREAD_POOL_STARTED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Runs a rayon thread spawned by `initThreadPool`, it never returns,
+/// throws for ids that were not handed to `spawn` or are already running
+#[wasm_bindgen(js_name = startWorker)]
+pub fn start_worker(thread: u32) -> Result<(), JsError> {
+    let builder = PENDING_THREADS.lock().unwrap().remove(&thread);
+    let builder = builder
+        .ok_or_else(|| JsError::new(&format!("Thread {thread} is unknown or already started")))?;
+    builder.run();
+    Ok(())
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Payment[]")]
+    pub type PaymentArray;
+
+    #[wasm_bindgen(typescript_type = "Signed<Payment>[]")]
+    pub type SignedPaymentArray;
+
+    #[wasm_bindgen(typescript_type = "Signed<Payment, SignatureLike>[]")]
+    pub type VerifiablePaymentArray;
+
+    #[wasm_bindgen(typescript_type = "boolean[]")]
+    pub type BooleanArray;
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Signs all payments with the same private key, in parallel, see `initThreadPool`
+    #[wasm_bindgen(js_name = signPayments)]
+    pub fn sign_payments(
+        &self,
+        payments: PaymentArray,
+        private_key: String,
+    ) -> Result<SignedPaymentArray, JsError> {
+        let private_key = Zeroizing::new(private_key);
+        let keypair = keypair_from_private_key(private_key.as_str()).map_err(map_js_err)?;
+        let client = self.client();
+        // js values are not `Send`, conversions happen on the calling thread
+        let payments = js_sys::Array::from(&payments)
+            .iter()
+            .map(|payment| client.resolve_valid_until(&payment))
+            .collect::<Result<js_sys::Array, _>>()?;
+        let payment_data: Vec<PaymentData> = from_js(&payments)?;
+        for (i, payment) in payment_data.iter().enumerate() {
+            client
+                .check_payment_data(payment)
+                .map_err(|e| JsError::new(&format!("payments[{i}]: {e}")))?;
+        }
+        let mina_payments = payment_data
+            .iter()
+            .map(MinaPayment::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let signatures = mina_payments
+            .par_iter()
+            .map(|payment| client.sign_payment(payment, &keypair))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(map_js_err)?;
+        let signed_payments = js_sys::Array::new();
+        for (signature, payment) in signatures.into_iter().zip(payments.iter()) {
+            signed_payments.push(&new_signed::<JsValue>(signature, &payment)?);
+        }
+        Ok(signed_payments.unchecked_into())
+    }
+
+    /// Verifies all signed payments in parallel, results keep the input order
+    #[wasm_bindgen(js_name = verifyPayments)]
+    pub fn verify_payments(
+        &self,
+        signed_payments: VerifiablePaymentArray,
+    ) -> Result<BooleanArray, JsError> {
+        let signed_payments: Vec<SignedData<PaymentData>> = from_js(&signed_payments)?;
+        let payloads = signed_payments
+            .iter()
+            .map(|signed| {
+                let signature: MinaSignature = (&signed.signature).try_into()?;
+                let public_key = parse_public_key(signed.data.from.as_str()).map_err(map_js_err)?;
+                let payment: MinaPayment = (&signed.data).try_into()?;
+                Ok((signature, public_key, payment))
+            })
+            .collect::<Result<Vec<_>, JsError>>()?;
+        let client = self.client();
+        let verified: Vec<bool> = payloads
+            .par_iter()
+            .map(|(signature, public_key, payment)| {
+                client.verify_payment_signature(signature, public_key, payment)
+            })
+            .collect();
+        Ok(to_js(&verified)?.unchecked_into())
+    }
+}
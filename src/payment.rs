@@ -1,8 +1,11 @@
 use crate::*;
 use mina_hasher::{Hashable, ROInput};
+#[cfg(feature = "serialization")]
 use mina_serialization_types::{common::*, json::*};
 use mina_signer::{CompressedPubKey, NetworkId};
+#[cfg(feature = "serialization")]
 use num_traits::identities::One;
+use serde::{Deserialize, Serialize};
 
 #[wasm_bindgen(typescript_custom_section)]
 const PAYMENT: &'static str = r#"
@@ -13,7 +16,7 @@ export interface Payment {
     amount: UInt64;
     nonce: UInt32;
     memo?: string;
-    validUntil?: UInt32;
+    validUntil?: ValidUntil;
 }
 "#;
 
@@ -22,124 +25,77 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "Payment")]
     pub type Payment;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn to(this: &Payment) -> String;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn from(this: &Payment) -> String;
-
-    // u64 can be either f64 or bigint in js,
-    // use JsValue to handle the conversion
-    #[wasm_bindgen(method, getter)]
-    pub fn fee(this: &Payment) -> JsValue;
-
-    // u64 can be either f64 or bigint in js,
-    // use JsValue to handle the conversion
-    #[wasm_bindgen(method, getter)]
-    pub fn amount(this: &Payment) -> JsValue;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn nonce(this: &Payment) -> JsValue;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn memo(this: &Payment) -> Option<String>;
-
-    #[wasm_bindgen(method, getter, js_name = validUntil)]
-    pub fn valid_until(this: &Payment) -> JsValue;
-
     #[wasm_bindgen(typescript_type = "Signed<Payment>")]
     pub type SignedPayment;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn signature(this: &SignedPayment) -> Signature;
+    #[wasm_bindgen(typescript_type = "Signed<Payment, SignatureLike>")]
+    pub type VerifiablePayment;
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn data(this: &SignedPayment) -> Payment;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentData {
+    pub to: String,
+    pub from: String,
+    // u64 can be either f64, bigint or string in js
+    #[serde(with = "uint")]
+    pub fee: u64,
+    #[serde(with = "uint")]
+    pub amount: u64,
+    #[serde(with = "uint")]
+    pub nonce: u32,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default, with = "option_uint")]
+    pub valid_until: Option<u32>,
 }
 
 impl Payment {
-    pub fn fee_u64(&self) -> Result<u64, JsError> {
-        js_to_string(&self.fee()).parse().map_err(map_js_err)
-    }
-
-    pub fn amount_u64(&self) -> Result<u64, JsError> {
-        js_to_string(&self.amount()).parse().map_err(map_js_err)
+    pub fn to_data(&self) -> Result<PaymentData, JsError> {
+        from_js(self)
     }
 
-    pub fn nonce_u32(&self) -> Result<u32, JsError> {
-        js_to_string(&self.nonce()).parse().map_err(map_js_err)
+    pub fn try_to_mina_payment(&self) -> Result<MinaPayment, JsError> {
+        (&self.to_data()?).try_into()
     }
+}
 
-    pub fn valid_until_u32(&self) -> Result<Option<u32>, JsError> {
-        let valid_until = self.valid_until();
-        if valid_until.is_null() || valid_until.is_undefined() {
-            Ok(None)
-        } else {
-            let s = js_to_string(&valid_until);
-            if s.is_empty() {
-                Ok(None)
-            } else {
-                Ok(Some(s.parse().map_err(map_js_err)?))
-            }
-        }
+impl SignedPayment {
+    pub fn to_data(&self) -> Result<SignedData<PaymentData>, JsError> {
+        from_js(self)
     }
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function new_payment(to, from, fee, amount, nonce, memo, validUntil) {
-            return {
-                to,
-                from,
-                fee,
-                amount,
-                nonce,
-                memo,
-                validUntil,
-            }
-        }
-
-        function new_signed_payment(signature, data) {
-            return {
-                signature,
-                data,
-            }
-        }
-
-        module.exports = {
-            new_payment, new_signed_payment
-        }
-    "#)]
-extern "C" {
-    pub fn new_payment(
-        to: String,
-        from: String,
-        fee: u64,
-        amount: u64,
-        nonce: u32,
-        memo: Option<String>,
-        valid_until: Option<u32>,
-    ) -> Payment;
-
-    pub fn new_signed_payment(signature: Signature, data: Payment) -> SignedPayment;
-}
+impl TryFrom<&PaymentData> for MinaPayment {
+    type Error = JsError;
 
-impl Payment {
-    pub fn try_to_mina_payment(&self) -> Result<MinaPayment, JsError> {
+    fn try_from(v: &PaymentData) -> Result<Self, Self::Error> {
         Ok(MinaPayment {
-            to: CompressedPubKey::from_address(self.to().as_str()).map_err(map_js_err)?,
-            from: CompressedPubKey::from_address(self.from().as_str()).map_err(map_js_err)?,
-            fee: self.fee_u64()?,
-            amount: self.amount_u64()?,
-            nonce: self.nonce_u32()?,
-            memo: string_to_memo(self.memo()),
-            valid_until: match self.valid_until_u32()? {
-                None => u32::max_value(),
-                Some(i) => i,
-            },
+            to: parse_compressed_public_key(v.to.as_str()).map_err(map_js_err)?,
+            from: parse_compressed_public_key(v.from.as_str()).map_err(map_js_err)?,
+            fee: v.fee,
+            amount: v.amount,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
         })
     }
 }
 
+impl From<&MinaPayment> for PaymentData {
+    fn from(v: &MinaPayment) -> Self {
+        Self {
+            to: v.to.into_address(),
+            from: v.from.into_address(),
+            fee: v.fee,
+            amount: v.amount,
+            nonce: v.nonce,
+            memo: memo_to_string(&v.memo),
+            valid_until: Some(v.valid_until),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MinaPayment {
     pub to: CompressedPubKey,
@@ -153,16 +109,10 @@ pub struct MinaPayment {
 
 impl From<MinaPayment> for Payment {
     fn from(v: MinaPayment) -> Self {
-        let memo = memo_to_string(&v.memo);
-        new_payment(
-            v.to.into_address(),
-            v.from.into_address(),
-            v.fee,
-            v.amount,
-            v.nonce,
-            memo,
-            Some(v.valid_until),
-        )
+        PaymentData::from(&v)
+            .serialize(&JS_SERIALIZER)
+            .expect("PaymentData is always serializable")
+            .unchecked_into()
     }
 }
 
@@ -174,6 +124,7 @@ impl TryFrom<Payment> for MinaPayment {
     }
 }
 
+#[cfg(feature = "serialization")]
 impl From<MinaPayment> for SignedCommandJson {
     fn from(p: MinaPayment) -> Self {
         let dummy_signature = MinaSignature {
@@ -203,22 +154,42 @@ impl From<MinaPayment> for SignedCommandJson {
     }
 }
 
-impl TryFrom<Payment> for SignedCommandJson {
+#[cfg(feature = "serialization")]
+impl TryFrom<&PaymentData> for SignedCommandJson {
     type Error = JsError;
 
-    fn try_from(v: Payment) -> Result<Self, Self::Error> {
+    fn try_from(v: &PaymentData) -> Result<Self, Self::Error> {
         let p: MinaPayment = v.try_into()?;
         Ok(p.into())
     }
 }
 
+#[cfg(feature = "serialization")]
+impl TryFrom<Payment> for SignedCommandJson {
+    type Error = JsError;
+
+    fn try_from(v: Payment) -> Result<Self, Self::Error> {
+        (&v.to_data()?).try_into()
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl TryFrom<&SignedData<PaymentData>> for SignedCommandJson {
+    type Error = JsError;
+
+    fn try_from(v: &SignedData<PaymentData>) -> Result<Self, Self::Error> {
+        let mut result: Self = (&v.data).try_into()?;
+        result.signature = signature_to_json((&v.signature).try_into()?);
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "serialization")]
 impl TryFrom<SignedPayment> for SignedCommandJson {
     type Error = JsError;
 
     fn try_from(v: SignedPayment) -> Result<Self, Self::Error> {
-        let mut result: Self = v.data().try_into()?;
-        result.signature = signature_to_json(v.signature().try_into()?);
-        Ok(result)
+        (&v.to_data()?).try_into()
     }
 }
 
@@ -0,0 +1,244 @@
+use crate::*;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::Mutex,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const SIGNING_POLICY: &'static str = r#"
+export interface SigningPolicy {
+    maxAmount?: UInt64;
+    maxFee?: UInt64;
+    allowedRecipients?: PublicKey[];
+    deniedRecipients?: PublicKey[];
+    allowedDelegates?: PublicKey[];
+    deniedDelegates?: PublicKey[];
+    /**
+     * The whole memo has to match at least one of the regular expressions, in the `regex` crate syntax
+     * with `\d`, `\w`, `\s` and `(?i)` but without unicode scripts or categories like `\p{Greek}`.
+     * Patterns are anchored, `invoice-\d+` does not accept `refund invoice-1`.
+     * Memos longer than 32 bytes are rejected instead of being cut before the check
+     */
+    memoPatterns?: string[];
+    /** validUntil has to be set and not exceed this slot */
+    maxValidUntil?: UInt32;
+    /** At most `maxSignatures` per signing key within any `intervalMs` window */
+    rateLimit?: { maxSignatures: number; intervalMs: number };
+}
+
+export interface ClientOptions {
+    /** Policy object or its JSON, evaluated before any payment or stake delegation is signed */
+    policy?: SigningPolicy | string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(method, getter)]
+    pub fn policy(this: &ClientOptions) -> JsValue;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    pub max_signatures: usize,
+    #[serde(with = "uint")]
+    pub interval_ms: u64,
+}
+
+/// Guardrails evaluated by `signPayment` and `signStakeDelegation` before a signature is produced
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SigningPolicy {
+    #[serde(default, with = "option_uint")]
+    pub max_amount: Option<u64>,
+    #[serde(default, with = "option_uint")]
+    pub max_fee: Option<u64>,
+    #[serde(default)]
+    pub allowed_recipients: Option<HashSet<String>>,
+    #[serde(default)]
+    pub denied_recipients: HashSet<String>,
+    #[serde(default)]
+    pub allowed_delegates: Option<HashSet<String>>,
+    #[serde(default)]
+    pub denied_delegates: HashSet<String>,
+    #[serde(default, deserialize_with = "deserialize_patterns")]
+    pub memo_patterns: Vec<Regex>,
+    #[serde(default, with = "option_uint")]
+    pub max_valid_until: Option<u32>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Signing timestamps (ms) per signer public key within the rate limit window
+    #[serde(skip)]
+    signed_at: Mutex<HashMap<String, VecDeque<f64>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation(pub String);
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Policy violation: {}", self.0)
+    }
+}
+
+/// Patterns are wrapped in `^(?:...)$` so that they match the whole memo
+fn deserialize_patterns<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Regex>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|pattern| Regex::new(&format!("^(?:{pattern})$")).map_err(de::Error::custom))
+        .collect()
+}
+
+impl SigningPolicy {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        from_json(json).map_err(|e| format!("Invalid signing policy: {e}"))
+    }
+
+    /// Reads `ClientOptions.policy`, which is either a policy object or its JSON
+    pub(crate) fn from_options(options: &ClientOptions) -> Result<Option<Self>, JsError> {
+        let policy = options.policy();
+        if policy.is_undefined() || policy.is_null() {
+            Ok(None)
+        } else if let Some(json) = policy.as_string() {
+            Self::from_json(json.as_str()).map(Some).map_err(map_js_err)
+        } else {
+            deserialize_js(&policy)
+                .map(Some)
+                .map_err(|e| JsError::new(&format!("Invalid signing policy: {e}")))
+        }
+    }
+
+    pub fn check_payment(
+        &self,
+        payment: &MinaPayment,
+        signer: &str,
+    ) -> Result<(), PolicyViolation> {
+        if let Some(max_amount) = self.max_amount {
+            if payment.amount > max_amount {
+                return violation(format!(
+                    "amount {} exceeds maxAmount {max_amount}",
+                    payment.amount
+                ));
+            }
+        }
+        let recipient = payment.to.into_address();
+        check_lists(
+            "recipient",
+            &recipient,
+            &self.allowed_recipients,
+            &self.denied_recipients,
+        )?;
+        self.check_common(payment.fee, &payment.memo, payment.valid_until)?;
+        self.check_rate_limit(signer)
+    }
+
+    pub fn check_stake_delegation(
+        &self,
+        stake_delegation: &MinaStakeDelegation,
+        signer: &str,
+    ) -> Result<(), PolicyViolation> {
+        let delegate = stake_delegation.to.into_address();
+        check_lists(
+            "delegate",
+            &delegate,
+            &self.allowed_delegates,
+            &self.denied_delegates,
+        )?;
+        self.check_common(
+            stake_delegation.fee,
+            &stake_delegation.memo,
+            stake_delegation.valid_until,
+        )?;
+        self.check_rate_limit(signer)
+    }
+
+    /// Rejects memos that would be cut to 32 bytes, memoPatterns would otherwise
+    /// be evaluated on a different memo than the one the caller passed
+    pub fn check_memo_length(&self, memo: Option<&str>) -> Result<(), PolicyViolation> {
+        let max_length = constants::MEMO_BYTES - 2;
+        match memo {
+            Some(memo) if !self.memo_patterns.is_empty() && memo.len() > max_length => {
+                violation(format!(
+                    "memo is {} bytes, memoPatterns only accept memos of at most {max_length} bytes",
+                    memo.len()
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_common(
+        &self,
+        fee: u64,
+        memo: &[u8; constants::MEMO_BYTES],
+        valid_until: u32,
+    ) -> Result<(), PolicyViolation> {
+        if let Some(max_fee) = self.max_fee {
+            if fee > max_fee {
+                return violation(format!("fee {fee} exceeds maxFee {max_fee}"));
+            }
+        }
+        if !self.memo_patterns.is_empty() {
+            let memo = memo_to_string(memo).unwrap_or_default();
+            if !self.memo_patterns.iter().any(|p| p.is_match(memo.as_str())) {
+                return violation(format!("memo '{memo}' matches none of memoPatterns"));
+            }
+        }
+        if let Some(max_valid_until) = self.max_valid_until {
+            if valid_until > max_valid_until {
+                return violation(format!(
+                    "validUntil {valid_until} exceeds maxValidUntil {max_valid_until}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the signature when it is within the limit
+    fn check_rate_limit(&self, signer: &str) -> Result<(), PolicyViolation> {
+        let Some(rate_limit) = &self.rate_limit else {
+            return Ok(());
+        };
+        let now = js_sys::Date::now();
+        let mut signed_at = self.signed_at.lock().unwrap_or_else(|e| e.into_inner());
+        let timestamps = signed_at.entry(signer.to_string()).or_default();
+        while matches!(timestamps.front(), Some(t) if now - t >= rate_limit.interval_ms as f64) {
+            timestamps.pop_front();
+        }
+        if timestamps.len() >= rate_limit.max_signatures {
+            return violation(format!(
+                "{signer} exceeded {} signatures per {}ms",
+                rate_limit.max_signatures, rate_limit.interval_ms
+            ));
+        }
+        timestamps.push_back(now);
+        Ok(())
+    }
+}
+
+fn check_lists(
+    name: &str,
+    address: &str,
+    allowed: &Option<HashSet<String>>,
+    denied: &HashSet<String>,
+) -> Result<(), PolicyViolation> {
+    if denied.contains(address) {
+        return violation(format!("{name} {address} is denied"));
+    }
+    match allowed {
+        Some(allowed) if !allowed.contains(address) => {
+            violation(format!("{name} {address} is not allowed"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn violation(reason: String) -> Result<(), PolicyViolation> {
+    Err(PolicyViolation(reason))
+}
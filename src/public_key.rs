@@ -0,0 +1,131 @@
+use crate::*;
+use mina_signer::{CompressedPubKey, PubKey};
+use serde::{Deserialize, Serialize};
+
+#[wasm_bindgen(typescript_custom_section)]
+const PUBLIC_KEY_FORMS: &'static str = r#"
+/** Affine coordinates, decimal or `0x` prefixed hex */
+export interface PublicKeyPoint {
+    x: string;
+    y: string;
+}
+
+/** Same shape as the JSON of an o1js `PublicKey` */
+export interface CompressedPublicKey {
+    x: string;
+    isOdd: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PublicKeyPoint")]
+    pub type PublicKeyPoint;
+
+    #[wasm_bindgen(typescript_type = "CompressedPublicKey")]
+    pub type CompressedPublicKey;
+
+    #[wasm_bindgen(typescript_type = r#""decimal" | "hex""#)]
+    pub type CoordinateFormat;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeyPointData {
+    pub x: String,
+    pub y: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressedPublicKeyData {
+    pub x: String,
+    pub is_odd: bool,
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// The point of a public key, `format` is `"decimal"` (default) or `"hex"`
+    #[wasm_bindgen(js_name = publicKeyToPoint)]
+    pub fn public_key_to_point(
+        &self,
+        public_key: &str,
+        format: Option<CoordinateFormat>,
+    ) -> Result<PublicKeyPoint, JsError> {
+        let hex = match format.and_then(|f| f.as_string()).as_deref() {
+            None | Some("decimal") => false,
+            Some("hex") => true,
+            Some(format) => {
+                return Err(JsError::new(&format!(
+                    "Invalid format '{format}', expected 'decimal' or 'hex'"
+                )))
+            }
+        };
+        let point = parse_public_key(public_key)
+            .map_err(map_js_err)?
+            .into_point();
+        let point = PublicKeyPointData {
+            x: prime_field_to_string(&point.x, hex),
+            y: prime_field_to_string(&point.y, hex),
+        };
+        Ok(to_js(&point)?.unchecked_into())
+    }
+
+    /// Inverse of `publicKeyToPoint`, the point has to be on the curve
+    #[wasm_bindgen(js_name = publicKeyFromPoint)]
+    pub fn public_key_from_point(&self, point: PublicKeyPoint) -> Result<String, JsError> {
+        let point: PublicKeyPointData = from_js(&point)?;
+        public_key_from_point(&point).map_err(map_js_err)
+    }
+
+    /// The x coordinate and the parity of y of a public key
+    #[wasm_bindgen(js_name = publicKeyToCompressed)]
+    pub fn public_key_to_compressed(
+        &self,
+        public_key: &str,
+    ) -> Result<CompressedPublicKey, JsError> {
+        // Decompressing validates that the key is on the curve
+        let pk = parse_public_key(public_key)
+            .map_err(map_js_err)?
+            .into_compressed();
+        let compressed = CompressedPublicKeyData {
+            x: prime_field_to_string(&pk.x, false),
+            is_odd: pk.is_odd,
+        };
+        Ok(to_js(&compressed)?.unchecked_into())
+    }
+
+    /// Inverse of `publicKeyToCompressed`, there has to be a point on the curve with this x
+    #[wasm_bindgen(js_name = publicKeyFromCompressed)]
+    pub fn public_key_from_compressed(
+        &self,
+        compressed: CompressedPublicKey,
+    ) -> Result<String, JsError> {
+        let compressed: CompressedPublicKeyData = from_js(&compressed)?;
+        public_key_from_compressed(&compressed).map_err(map_js_err)
+    }
+}
+
+pub(crate) fn public_key_from_point(point: &PublicKeyPointData) -> Result<String, String> {
+    let point = CurvePoint::new(
+        prime_field_from_str("x", &point.x)?,
+        prime_field_from_str("y", &point.y)?,
+        false,
+    );
+    if !point.is_on_curve() {
+        return Err("Point is not on the curve".into());
+    }
+    Ok(PubKey::from_point_unsafe(point).into_address())
+}
+
+pub(crate) fn public_key_from_compressed(
+    compressed: &CompressedPublicKeyData,
+) -> Result<String, String> {
+    let address = CompressedPubKey {
+        x: prime_field_from_str("x", &compressed.x)?,
+        is_odd: compressed.is_odd,
+    }
+    .into_address();
+    parse_public_key(&address)
+        .map_err(|_| format!("No point on the curve has x {}", compressed.x))?;
+    Ok(address)
+}
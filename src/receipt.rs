@@ -0,0 +1,112 @@
+use crate::*;
+use mina_hasher::{Hashable, Hasher, ROInput};
+
+type Field = <CurvePoint as AffineCurve>::BaseField;
+
+#[wasm_bindgen]
+impl Client {
+    /// The receipt chain hash of an account after it sent `transaction`,
+    /// `previousHash` is its receipt chain hash before
+    #[wasm_bindgen(js_name = receiptChainHash)]
+    pub fn receipt_chain_hash(
+        &self,
+        previous_hash: &str,
+        transaction: Transaction,
+    ) -> Result<String, JsError> {
+        let previous = decode_receipt_chain_hash(previous_hash).map_err(map_js_err)?;
+        let payload = ReceiptChainPayload::parse(self.client(), &transaction)?;
+        Ok(encode_receipt_chain_hash(&payload.cons(previous)))
+    }
+
+    /// Whether applying `transactions` in order to `initialHash` ends at `finalHash`,
+    /// e.g. the receipt chain hash of the account on chain
+    #[wasm_bindgen(js_name = verifyReceiptChain)]
+    pub fn verify_receipt_chain(
+        &self,
+        initial_hash: &str,
+        transactions: TransactionArray,
+        final_hash: &str,
+    ) -> Result<bool, JsError> {
+        let mut hash = decode_receipt_chain_hash(initial_hash).map_err(map_js_err)?;
+        let expected = decode_receipt_chain_hash(final_hash).map_err(map_js_err)?;
+        for (i, transaction) in js_sys::Array::from(&transactions).iter().enumerate() {
+            let payload = ReceiptChainPayload::parse(self.client(), &transaction)
+                .map_err(|_| JsError::new(&format!("Invalid transaction at index {i}")))?;
+            hash = payload.cons(hash);
+        }
+        Ok(hash == expected)
+    }
+}
+
+/// The receipt chain hash of accounts that never sent a transaction
+#[wasm_bindgen(js_name = emptyReceiptChainHash)]
+pub fn empty_receipt_chain_hash() -> String {
+    constants::EMPTY_RECEIPT_CHAIN_HASH.into()
+}
+
+#[derive(Debug, Clone)]
+pub enum ReceiptChainPayload {
+    Payment(MinaPayment),
+    StakeDelegation(MinaStakeDelegation),
+}
+
+impl ReceiptChainPayload {
+    /// Resolves `validUntil: { expiresAt }` on the network of `client`
+    fn parse(client: &ClientImpl, transaction: &JsValue) -> Result<Self, JsError> {
+        let transaction = client.resolve_transaction_valid_until(transaction)?;
+        let data = TransactionData::parse(&transaction)?.into_unsigned();
+        Ok(match data {
+            UnsignedTransactionData::Payment(p) => Self::Payment((&p).try_into()?),
+            UnsignedTransactionData::StakeDelegation(d) => Self::StakeDelegation((&d).try_into()?),
+        })
+    }
+
+    /// The next receipt chain hash, legacy poseidon over the payload followed by the previous hash
+    pub fn cons(&self, previous: Field) -> Field {
+        let element = ReceiptChainElement {
+            payload: self,
+            previous,
+        };
+        mina_hasher::create_legacy::<ReceiptChainElement>(()).hash(&element)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReceiptChainElement<'a> {
+    payload: &'a ReceiptChainPayload,
+    previous: Field,
+}
+
+impl Hashable for ReceiptChainElement<'_> {
+    type D = ();
+
+    fn to_roinput(&self) -> ROInput {
+        let mut roi = match self.payload {
+            ReceiptChainPayload::Payment(p) => p.to_roinput(),
+            ReceiptChainPayload::StakeDelegation(d) => d.to_roinput(),
+        };
+        roi.append_field(self.previous);
+        roi
+    }
+
+    fn domain_string(_: ()) -> Option<String> {
+        "CodaReceiptUC".to_string().into()
+    }
+}
+
+/// Receipt chain hashes are the version tag followed by the field in little endian
+pub(crate) fn decode_receipt_chain_hash(value: &str) -> Result<Field, String> {
+    let decoded = decode_base58_check(value, Some(Base58Kind::ReceiptChainHash))
+        .map_err(|e| format!("Invalid receipt chain hash: {e}"))?;
+    match decoded.bytes.split_first() {
+        Some((1, field)) if field.len() == 32 => Field::from_bytes(field)
+            .map_err(|_| "Invalid receipt chain hash: field is out of range".into()),
+        _ => Err("Invalid receipt chain hash: unexpected length or version tag".into()),
+    }
+}
+
+pub(crate) fn encode_receipt_chain_hash(hash: &Field) -> String {
+    let mut bytes = vec![1];
+    bytes.extend(hash.to_bytes());
+    encode_base58_check(Base58Kind::ReceiptChainHash, &bytes)
+}
@@ -1,127 +1,250 @@
 use crate::*;
+use js_sys::Reflect;
 use mina_serialization_types::json::*;
-use mina_signer::CompressedPubKey;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt::Display;
 
 #[wasm_bindgen]
 extern "C" {
-    pub type SignedRosettaTransaction;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn signature(this: &SignedRosettaTransaction) -> String;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn payment(this: &SignedRosettaTransaction) -> Option<Payment>;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn stake_delegation(this: &SignedRosettaTransaction) -> Option<RosettaStakeDelegation>;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn create_token(this: &SignedRosettaTransaction) -> Option<String>;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn create_token_account(this: &SignedRosettaTransaction) -> Option<String>;
+    #[wasm_bindgen(
+        typescript_type = "Signed<Payment, SignatureLike> | Signed<StakeDelegation, SignatureLike> | object | string"
+    )]
+    pub type SignedCommandLike;
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn mint_tokens(this: &SignedRosettaTransaction) -> Option<String>;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedRosettaTransaction {
+    /// Rosetta hex
+    pub signature: String,
+    #[serde(default)]
+    pub payment: Option<RosettaPayment>,
+    #[serde(default)]
+    pub stake_delegation: Option<RosettaStakeDelegation>,
+    /// Always null, part of the shape Rosetta returns
+    #[serde(default, skip_deserializing)]
+    pub create_token: Option<()>,
+    #[serde(default, skip_deserializing)]
+    pub create_token_account: Option<()>,
+    #[serde(default, skip_deserializing)]
+    pub mint_tokens: Option<()>,
+}
 
-    pub type RosettaStakeDelegation;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosettaPayment {
+    pub to: String,
+    pub from: String,
+    // u64 can be either number or string in json, rosetta writes strings
+    #[serde(deserialize_with = "uint::deserialize", serialize_with = "to_string")]
+    pub fee: u64,
+    #[serde(default = "default_token", skip_deserializing)]
+    pub token: String,
+    #[serde(deserialize_with = "uint::deserialize", serialize_with = "to_string")]
+    pub nonce: u32,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(deserialize_with = "uint::deserialize", serialize_with = "to_string")]
+    pub amount: u64,
+    #[serde(
+        default,
+        deserialize_with = "option_uint::deserialize",
+        serialize_with = "option_to_string",
+        alias = "validUntil"
+    )]
+    pub valid_until: Option<u32>,
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn new_delegate(this: &RosettaStakeDelegation) -> String;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosettaStakeDelegation {
+    pub delegator: String,
+    pub new_delegate: String,
+    // u64 can be either number or string in json, rosetta writes strings
+    #[serde(deserialize_with = "uint::deserialize", serialize_with = "to_string")]
+    pub fee: u64,
+    #[serde(deserialize_with = "uint::deserialize", serialize_with = "to_string")]
+    pub nonce: u32,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "option_uint::deserialize",
+        serialize_with = "option_to_string",
+        alias = "validUntil"
+    )]
+    pub valid_until: Option<u32>,
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn delegator(this: &RosettaStakeDelegation) -> String;
+fn default_token() -> String {
+    "1".into()
+}
 
-    // u64 can be either f64 or bigint in js,
-    // use JsValue to handle the conversion
-    #[wasm_bindgen(method, getter)]
-    pub fn fee(this: &RosettaStakeDelegation) -> JsValue;
+fn to_string<S: Serializer, T: Display>(v: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(v)
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn nonce(this: &RosettaStakeDelegation) -> JsValue;
+fn option_to_string<S: Serializer, T: Display>(
+    v: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match v {
+        Some(v) => serializer.collect_str(v),
+        None => serializer.serialize_none(),
+    }
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn memo(this: &RosettaStakeDelegation) -> Option<String>;
+impl TryFrom<&RosettaPayment> for MinaPayment {
+    type Error = JsError;
 
-    #[wasm_bindgen(method, getter, js_name = validUntil)]
-    pub fn valid_until(this: &RosettaStakeDelegation) -> JsValue;
+    fn try_from(v: &RosettaPayment) -> Result<Self, Self::Error> {
+        Ok(MinaPayment {
+            to: parse_compressed_public_key(v.to.as_str()).map_err(map_js_err)?,
+            from: parse_compressed_public_key(v.from.as_str()).map_err(map_js_err)?,
+            fee: v.fee,
+            amount: v.amount,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
+        })
+    }
 }
 
-impl RosettaStakeDelegation {
-    pub fn fee_u64(&self) -> Result<u64, JsError> {
-        js_to_string(&self.fee()).parse().map_err(map_js_err)
-    }
+impl TryFrom<&RosettaPayment> for SignedCommandJson {
+    type Error = JsError;
 
-    pub fn nonce_u32(&self) -> Result<u32, JsError> {
-        js_to_string(&self.nonce()).parse().map_err(map_js_err)
+    fn try_from(v: &RosettaPayment) -> Result<Self, Self::Error> {
+        let p: MinaPayment = v.try_into()?;
+        Ok(p.into())
     }
+}
 
-    pub fn valid_until_u32(&self) -> Result<Option<u32>, JsError> {
-        let valid_until = self.valid_until();
-        if valid_until.is_null() || valid_until.is_undefined() {
-            Ok(None)
-        } else {
-            let s = js_to_string(&valid_until);
-            if s.is_empty() {
-                Ok(None)
-            } else {
-                Ok(Some(s.parse().map_err(map_js_err)?))
-            }
-        }
-    }
+impl TryFrom<&RosettaStakeDelegation> for MinaStakeDelegation {
+    type Error = JsError;
 
-    pub fn try_to_mina_stake_delegation(&self) -> Result<MinaStakeDelegation, JsError> {
+    fn try_from(v: &RosettaStakeDelegation) -> Result<Self, Self::Error> {
         Ok(MinaStakeDelegation {
-            to: CompressedPubKey::from_address(self.new_delegate().as_str()).map_err(map_js_err)?,
-            from: CompressedPubKey::from_address(self.delegator().as_str()).map_err(map_js_err)?,
-            fee: self.fee_u64()?,
-            nonce: self.nonce_u32()?,
-            memo: string_to_memo(self.memo()),
-            valid_until: match self.valid_until_u32()? {
-                None => u32::max_value(),
-                Some(i) => i,
-            },
+            to: parse_compressed_public_key(v.new_delegate.as_str()).map_err(map_js_err)?,
+            from: parse_compressed_public_key(v.delegator.as_str()).map_err(map_js_err)?,
+            fee: v.fee,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
         })
     }
 }
 
-impl TryFrom<RosettaStakeDelegation> for MinaStakeDelegation {
+impl TryFrom<&RosettaStakeDelegation> for SignedCommandJson {
     type Error = JsError;
 
-    fn try_from(v: RosettaStakeDelegation) -> Result<Self, Self::Error> {
-        v.try_to_mina_stake_delegation()
+    fn try_from(v: &RosettaStakeDelegation) -> Result<Self, Self::Error> {
+        let p: MinaStakeDelegation = v.try_into()?;
+        Ok(p.into())
     }
 }
 
-impl TryFrom<RosettaStakeDelegation> for SignedCommandJson {
-    type Error = JsError;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedCommandGraphQLJson {
+    pub data: SignedCommandJson,
+}
 
-    fn try_from(v: RosettaStakeDelegation) -> Result<Self, Self::Error> {
-        let p: MinaStakeDelegation = v.try_into()?;
-        Ok(p.into())
+impl From<&MinaPayment> for RosettaPayment {
+    fn from(v: &MinaPayment) -> Self {
+        Self {
+            to: v.to.into_address(),
+            from: v.from.into_address(),
+            fee: v.fee,
+            token: default_token(),
+            nonce: v.nonce,
+            memo: memo_to_string(&v.memo),
+            amount: v.amount,
+            valid_until: Some(v.valid_until),
+        }
     }
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function signed_rosetta_transaction_from_str(s) {
-            try {
-                return JSON.parse(s)
-            } catch(e) {
-                console.log(`signed_rosetta_transaction_from_str: ${e}`)
-                return null
-            }
+impl From<&MinaStakeDelegation> for RosettaStakeDelegation {
+    fn from(v: &MinaStakeDelegation) -> Self {
+        Self {
+            delegator: v.from.into_address(),
+            new_delegate: v.to.into_address(),
+            fee: v.fee,
+            nonce: v.nonce,
+            memo: memo_to_string(&v.memo),
+            valid_until: Some(v.valid_until),
         }
+    }
+}
 
-        module.exports = {
-            signed_rosetta_transaction_from_str
+impl SignedRosettaTransaction {
+    pub fn new(payload: &UserCommandPayload, signature: &MinaSignature) -> Self {
+        let (payment, stake_delegation) = match payload {
+            UserCommandPayload::Payment(p) => (Some(p.into()), None),
+            UserCommandPayload::StakeDelegation(d) => (None, Some(d.into())),
+        };
+        Self {
+            signature: encode_signature_rosetta_hex(signature),
+            payment,
+            stake_delegation,
+            create_token: None,
+            create_token_account: None,
+            mint_tokens: None,
         }
-    "#)]
-extern "C" {
-    pub fn signed_rosetta_transaction_from_str(s: String) -> Option<SignedRosettaTransaction>;
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SignedCommandGraphQLJson {
-    pub data: SignedCommandJson,
+/// Payload and signature of `Signed<Payment>` / `Signed<StakeDelegation>`, or of a GraphQL
+/// signed command (as JSON or object, with or without the `data` wrapper),
+/// `validUntil: { expiresAt }` is resolved on the network of `client`
+pub(crate) fn parse_signed_command_like(
+    client: &ClientImpl,
+    value: &JsValue,
+) -> Result<(UserCommandPayload, MinaSignature), JsError> {
+    let signed: SignedData<UnsignedTransactionData> = match value.as_string() {
+        Some(json) => {
+            let mut json: Value = from_json(json.as_str()).map_err(map_js_err)?;
+            if !(json.get("signature").is_some() && json.get("data").is_some()) {
+                return decode_graphql_signed_command(json);
+            }
+            if let Some(valid_until) = json.pointer_mut("/data/validUntil") {
+                if let Some(slot) = client.expires_at_slot(
+                    &valid_until
+                        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+                        .map_err(map_js_err)?,
+                )? {
+                    *valid_until = slot.into();
+                }
+            }
+            serde_json::from_value(json).map_err(map_js_err)?
+        }
+        None => {
+            let is_signed_data = Reflect::has(value, &"signature".into()).unwrap_or(false)
+                && Reflect::has(value, &"data".into()).unwrap_or(false);
+            if !is_signed_data {
+                return decode_graphql_signed_command(from_js(value)?);
+            }
+            from_js(&client.resolve_transaction_valid_until(value)?)?
+        }
+    };
+    let payload = match &signed.data {
+        UnsignedTransactionData::Payment(p) => UserCommandPayload::Payment(p.try_into()?),
+        UnsignedTransactionData::StakeDelegation(d) => {
+            UserCommandPayload::StakeDelegation(d.try_into()?)
+        }
+    };
+    Ok((payload, (&signed.signature).try_into()?))
+}
+
+fn decode_graphql_signed_command(
+    json: Value,
+) -> Result<(UserCommandPayload, MinaSignature), JsError> {
+    let command = match json {
+        Value::Object(mut wrapper) if wrapper.contains_key("data") => {
+            wrapper.remove("data").expect("checked")
+        }
+        command => command,
+    };
+    let command: SignedCommandJsonData = serde_json::from_value(command)
+        .map_err(|e| JsError::new(&format!("Invalid signed command: {e}")))?;
+    let command = DecodedSignedCommand::try_from(&command)
+        .map_err(|e| JsError::new(&format!("Invalid signed command: {e}")))?;
+    Ok((command.payload, command.signature))
 }
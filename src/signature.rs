@@ -1,7 +1,11 @@
 use crate::*;
 use ark_ff::BigInteger256;
 use num_bigint::BigUint;
-use num_traits::Num;
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
 
 #[wasm_bindgen(typescript_custom_section)]
 const SIGNATURE: &'static str = r#"
@@ -10,8 +14,14 @@ export interface Signature {
     scalar: string;
 }
 
-export interface Signed<SignableData> {
-    signature: Signature;
+/**
+ * A signature in any of its encodings, decimal `{ field, scalar }`, base58 as shown by explorers,
+ * 128 character Rosetta hex, or 64 bytes (`field` then `scalar`, little endian)
+ */
+export type SignatureLike = Signature | string | Uint8Array;
+
+export interface Signed<SignableData, S = Signature> {
+    signature: S;
     data: SignableData;
 }
 "#;
@@ -21,47 +31,267 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "Signature")]
     pub type Signature;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn field(this: &Signature) -> String;
+    #[wasm_bindgen(typescript_type = "SignatureLike")]
+    pub type SignatureLike;
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn scalar(this: &Signature) -> String;
+/// Deserialized from any of the [SignatureLike] encodings, range checks are left to
+/// the conversion into [MinaSignature]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignatureData {
+    pub field: String,
+    pub scalar: String,
+}
 
-    pub type SignatureWrapper;
+impl SignatureData {
+    /// 32 bytes `field` then 32 bytes `scalar`, little endian
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != 64 {
+            return Err(format!(
+                "Invalid signature length: {} bytes, expected 64",
+                bytes.len()
+            ));
+        }
+        Ok(Self {
+            field: BigUint::from_bytes_le(&bytes[..32]).to_str_radix(10),
+            scalar: BigUint::from_bytes_le(&bytes[32..]).to_str_radix(10),
+        })
+    }
 
-    #[wasm_bindgen(method, getter)]
-    pub fn string(this: &SignatureWrapper) -> String;
+    pub fn from_base58(signature: &str) -> Result<Self, String> {
+        let decoded = bs58::decode(signature)
+            .with_check(Some(constants::SIGNATURE_BASE58_CHECK_VERSION_BYTE))
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 signature: {e}"))?;
+        match decoded.get(1) {
+            Some(1) => Self::from_bytes(&decoded[2..]),
+            _ => Err("Invalid base58 signature: unsupported version".into()),
+        }
+    }
 
-    #[wasm_bindgen(method, getter)]
-    pub fn signer(this: &SignatureWrapper) -> String;
+    /// `field` then `scalar`, 64 big endian hex characters each
+    pub fn from_rosetta_hex(signature: &str) -> Result<Self, String> {
+        if signature.len() != 128 {
+            return Err(format!(
+                "Invalid Rosetta signature: {} characters, expected 128",
+                signature.len()
+            ));
+        }
+        let mut bytes =
+            hex::decode(signature).map_err(|e| format!("Invalid Rosetta signature: {e}"))?;
+        bytes[..32].reverse();
+        bytes[32..].reverse();
+        Self::from_bytes(&bytes)
+    }
 
-    #[wasm_bindgen(method, getter)]
-    pub fn signature(this: &SignatureWrapper) -> Signature;
+    /// Rosetta hex when it is 128 hex characters, base58 otherwise
+    pub fn from_encoded(signature: &str) -> Result<Self, String> {
+        if signature.len() == 128 && signature.bytes().all(|b| b.is_ascii_hexdigit()) {
+            Self::from_rosetta_hex(signature)
+        } else {
+            Self::from_base58(signature)
+        }
+    }
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function new_signature(field, scalar) {
-            return {
-                field,
-                scalar,
-            }
+struct SignatureVisitor;
+
+impl<'de> Visitor<'de> for SignatureVisitor {
+    type Value = SignatureData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(
+            "a signature as { field, scalar }, a base58 or Rosetta hex string, or 64 bytes",
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        SignatureData::from_encoded(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        SignatureData::from_bytes(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(64);
+        while let Some(b) = seq.next_element::<u8>()? {
+            bytes.push(b);
         }
+        SignatureData::from_bytes(&bytes).map_err(de::Error::custom)
+    }
 
-        module.exports = {
-            new_signature,
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut field, mut scalar) = (None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "field" => field = Some(map.next_value()?),
+                "scalar" => scalar = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
         }
-    "#)]
-extern "C" {
-    pub fn new_signature(field: String, scalar: String) -> Signature;
+        Ok(SignatureData {
+            field: field.ok_or_else(|| de::Error::missing_field("field"))?,
+            scalar: scalar.ok_or_else(|| de::Error::missing_field("scalar"))?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SignatureData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SignatureVisitor)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedData<T> {
+    pub signature: SignatureData,
+    pub data: T,
+}
+
+/// Signed data that keeps the original js `data` object untouched
+#[derive(Serialize)]
+struct SignedJsData {
+    signature: SignatureData,
+    #[serde(serialize_with = "serde_wasm_bindgen::preserve::serialize")]
+    data: JsValue,
+}
+
+pub(crate) fn new_signed<T: JsCast>(
+    signature: MinaSignature,
+    data: &JsValue,
+) -> Result<T, JsError> {
+    let signed = SignedJsData {
+        signature: signature.into(),
+        data: data.clone(),
+    };
+    Ok(to_js(&signed)?.unchecked_into())
+}
+
+/// Converts a signature into its decimal `(field, scalar)` representation
+pub(crate) fn signature_to_decimal(value: &MinaSignature) -> (String, String) {
+    let field: BigInteger256 = value.rx.into();
+    let field: BigUint = field.into();
+    let scalar: BigInteger256 = value.s.into();
+    let scalar: BigUint = scalar.into();
+    (field.to_str_radix(10), scalar.to_str_radix(10))
+}
+
+/// Parses the decimal `field` of a signature, an element of the pallas base field
+pub(crate) fn signature_field_from_decimal(
+    field: &str,
+) -> Result<<CurvePoint as AffineCurve>::BaseField, String> {
+    prime_field_from_str("field", field)
+}
+
+/// Parses the decimal `scalar` of a signature, an element of the pallas scalar field
+pub(crate) fn signature_scalar_from_decimal(
+    scalar: &str,
+) -> Result<<CurvePoint as AffineCurve>::ScalarField, String> {
+    prime_field_from_str("scalar", scalar)
+}
+
+/// Parses a signature from its decimal `(field, scalar)` representation
+pub(crate) fn signature_from_decimal(field: &str, scalar: &str) -> Result<MinaSignature, String> {
+    Ok(MinaSignature {
+        rx: signature_field_from_decimal(field)?,
+        s: signature_scalar_from_decimal(scalar)?,
+    })
+}
+
+/// 32 bytes `rx` then 32 bytes `s`, little endian
+pub(crate) fn signature_to_bytes(signature: &MinaSignature) -> Vec<u8> {
+    let mut bytes = signature.rx.to_bytes();
+    bytes.extend(signature.s.to_bytes());
+    bytes
+}
+
+pub(crate) fn encode_signature_base58(signature: &MinaSignature) -> String {
+    // version of the serialized signature
+    let mut bytes = vec![1];
+    bytes.extend(signature_to_bytes(signature));
+    bs58::encode(bytes)
+        .with_check_version(constants::SIGNATURE_BASE58_CHECK_VERSION_BYTE)
+        .into_string()
+}
+
+pub(crate) fn encode_signature_rosetta_hex(signature: &MinaSignature) -> String {
+    let mut bytes = signature_to_bytes(signature);
+    bytes[..32].reverse();
+    bytes[32..].reverse();
+    hex::encode(bytes)
+}
+
+impl SignatureLike {
+    pub fn to_mina_signature(&self) -> Result<MinaSignature, JsError> {
+        let signature: SignatureData = from_js(self)?;
+        (&signature).try_into()
+    }
+}
+
+fn decoded_signature(signature: Result<SignatureData, String>) -> Result<Signature, JsError> {
+    let signature: MinaSignature = (&signature.map_err(map_js_err)?).try_into()?;
+    Ok(signature.into())
+}
+
+/// Base58 form of a signature, as shown by block explorers
+#[wasm_bindgen(js_name = signatureToBase58)]
+pub fn signature_to_base58(signature: SignatureLike) -> Result<String, JsError> {
+    Ok(encode_signature_base58(&signature.to_mina_signature()?))
+}
+
+#[wasm_bindgen(js_name = signatureFromBase58)]
+pub fn signature_from_base58(signature: &str) -> Result<Signature, JsError> {
+    decoded_signature(SignatureData::from_base58(signature))
+}
+
+/// 128 character hex form of a signature used by Rosetta
+#[wasm_bindgen(js_name = signatureToRosettaHex)]
+pub fn signature_to_rosetta_hex(signature: SignatureLike) -> Result<String, JsError> {
+    Ok(encode_signature_rosetta_hex(
+        &signature.to_mina_signature()?,
+    ))
+}
+
+#[wasm_bindgen(js_name = signatureFromRosettaHex)]
+pub fn signature_from_rosetta_hex(signature: &str) -> Result<Signature, JsError> {
+    decoded_signature(SignatureData::from_rosetta_hex(signature))
+}
+
+/// 64 bytes, `field` then `scalar`, little endian
+#[wasm_bindgen(js_name = signatureToBytes)]
+pub fn signature_to_bytes_js(signature: SignatureLike) -> Result<Vec<u8>, JsError> {
+    Ok(signature_to_bytes(&signature.to_mina_signature()?))
+}
+
+#[wasm_bindgen(js_name = signatureFromBytes)]
+pub fn signature_from_bytes(bytes: &[u8]) -> Result<Signature, JsError> {
+    decoded_signature(SignatureData::from_bytes(bytes))
+}
+
+impl From<MinaSignature> for SignatureData {
+    fn from(value: MinaSignature) -> Self {
+        let (field, scalar) = signature_to_decimal(&value);
+        Self { field, scalar }
+    }
+}
+
+impl TryFrom<&SignatureData> for MinaSignature {
+    type Error = JsError;
+
+    fn try_from(value: &SignatureData) -> Result<Self, Self::Error> {
+        signature_from_decimal(value.field.as_str(), value.scalar.as_str()).map_err(map_js_err)
+    }
 }
 
 impl From<MinaSignature> for Signature {
     fn from(value: MinaSignature) -> Self {
-        let field: BigInteger256 = value.rx.into();
-        let field: BigUint = field.into();
-        let scalar: BigInteger256 = value.s.into();
-        let scalar: BigUint = scalar.into();
-        new_signature(field.to_str_radix(10), scalar.to_str_radix(10))
+        SignatureData::from(value)
+            .serialize(&JS_SERIALIZER)
+            .expect("SignatureData is always serializable")
+            .unchecked_into()
     }
 }
 
@@ -69,18 +299,8 @@ impl TryFrom<Signature> for MinaSignature {
     type Error = JsError;
 
     fn try_from(value: Signature) -> Result<Self, Self::Error> {
-        let field: BigInteger256 = BigUint::from_str_radix(value.field().as_str(), 10)
-            .map_err(map_js_err)?
-            .try_into()
-            .map_err(map_js_err)?;
-        let scalar: BigInteger256 = BigUint::from_str_radix(value.scalar().as_str(), 10)
-            .map_err(map_js_err)?
-            .try_into()
-            .map_err(map_js_err)?;
-        Ok(MinaSignature {
-            rx: field.into(),
-            s: scalar.into(),
-        })
+        let signature: SignatureData = from_js(&value)?;
+        (&signature).try_into()
     }
 }
 
@@ -93,13 +313,40 @@ mod tests {
     pub fn signature_convert_roundtrip() {
         let field = "7951369555944720312047598238918799034092275686554435996661497930824346682019";
         let scalar = "7951369555944720312047598238918799034092275686554435996661497930824346682019";
-        let sig_js = new_signature(field.into(), scalar.into());
+        let sig_js: Signature = to_js(&SignatureData {
+            field: field.into(),
+            scalar: scalar.into(),
+        })
+        .map_err(|_| "fail to convert sig_data into sig_js")
+        .unwrap()
+        .unchecked_into();
         let sig_rs: MinaSignature = sig_js
             .try_into()
             .map_err(|_| "fail to convert sig_js into sig_rs")
             .unwrap();
         let sig_js_2: Signature = sig_rs.into();
-        assert_eq!(&sig_js_2.field(), field);
-        assert_eq!(&sig_js_2.scalar(), scalar);
+        let sig_data_2: SignatureData = from_js(&sig_js_2)
+            .map_err(|_| "fail to convert sig_js_2 into sig_data_2")
+            .unwrap();
+        assert_eq!(&sig_data_2.field, field);
+        assert_eq!(&sig_data_2.scalar, scalar);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn signature_encodings_roundtrip() {
+        let hex = "389ac7d4077f3d485c1494782870979faa222cd906b25b2687333a92f41e40b925adb08705eddf2a7098e5ac9938498e8a0ce7c70b25ea392f4846b854086d43";
+        let data = SignatureData::from_rosetta_hex(hex).unwrap();
+        let signature: MinaSignature = (&data).try_into().unwrap();
+        assert_eq!(encode_signature_rosetta_hex(&signature), hex);
+
+        let base58 = encode_signature_base58(&signature);
+        assert_eq!(SignatureData::from_encoded(&base58).unwrap(), data);
+        assert_eq!(SignatureData::from_encoded(hex).unwrap(), data);
+        assert_eq!(
+            SignatureData::from_bytes(&signature_to_bytes(&signature)).unwrap(),
+            data
+        );
+        assert!(SignatureData::from_base58(&hex[..20]).is_err());
+        assert!(SignatureData::from_bytes(&[0; 63]).is_err());
     }
 }
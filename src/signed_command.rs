@@ -0,0 +1,189 @@
+use crate::*;
+use mina_signer::PubKey;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The daemon's JSON of a signed command, the `data` of `SignedCommandGraphQLJson`
+/// and the commands of precomputed blocks
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignedCommandJsonData {
+    pub payload: SignedCommandPayloadJsonData,
+    pub signer: String,
+    /// Base58
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignedCommandPayloadJsonData {
+    pub common: SignedCommandCommonJsonData,
+    /// `["Payment", payment]` or `["Stake_delegation", ["Set_delegate", delegation]]`
+    pub body: (String, Value),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignedCommandCommonJsonData {
+    /// MINA
+    #[serde(with = "mina_amount")]
+    pub fee: u64,
+    #[serde(default)]
+    pub fee_token: Option<Value>,
+    pub fee_payer_pk: String,
+    #[serde(with = "uint")]
+    pub nonce: u32,
+    #[serde(with = "uint")]
+    pub valid_until: u32,
+    /// Base58
+    pub memo: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PaymentPayloadJsonData {
+    source_pk: String,
+    receiver_pk: String,
+    #[serde(default)]
+    token_id: Option<Value>,
+    /// Nanomina, unlike the fee
+    #[serde(with = "uint")]
+    amount: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SetDelegateJsonData {
+    delegator: String,
+    new_delegate: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum UserCommandPayload {
+    Payment(MinaPayment),
+    StakeDelegation(MinaStakeDelegation),
+}
+
+/// A signed command with its payload, keys and signature decoded
+#[cfg_attr(not(feature = "audit"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct DecodedSignedCommand {
+    pub payload: UserCommandPayload,
+    pub from: String,
+    pub to: String,
+    pub signer: PubKey,
+    pub signature: MinaSignature,
+}
+
+impl TryFrom<&SignedCommandJsonData> for DecodedSignedCommand {
+    type Error = String;
+
+    fn try_from(command: &SignedCommandJsonData) -> Result<Self, Self::Error> {
+        let common = &command.payload.common;
+        check_default_token(common.fee_token.as_ref())?;
+        if command.signer != common.fee_payer_pk {
+            return Err(format!(
+                "signer {} is not the fee payer {}",
+                command.signer, common.fee_payer_pk
+            ));
+        }
+        let memo: [u8; constants::MEMO_BYTES] =
+            decode_base58_check(&common.memo, Some(Base58Kind::Memo))
+                .map_err(|e| format!("Invalid memo: {e}"))?
+                .bytes
+                .try_into()
+                .map_err(|_| "Invalid memo: unexpected length")?;
+        if memo[1] as usize > constants::MEMO_BYTES - 2 {
+            return Err(format!(
+                "Invalid memo: length {} exceeds {} bytes",
+                memo[1],
+                constants::MEMO_BYTES - 2
+            ));
+        }
+        let fee_payer = parse_compressed_public_key(&common.fee_payer_pk)?;
+        let (tag, body) = &command.payload.body;
+        let (payload, to) = match tag.as_str() {
+            "Payment" => {
+                let payment: PaymentPayloadJsonData =
+                    serde_json::from_value(body.clone()).map_err(|e| e.to_string())?;
+                check_default_token(payment.token_id.as_ref())?;
+                if payment.source_pk != common.fee_payer_pk {
+                    return Err(
+                        "payments from a source other than the fee payer are not supported".into(),
+                    );
+                }
+                let payment_to = parse_compressed_public_key(&payment.receiver_pk)?;
+                (
+                    UserCommandPayload::Payment(MinaPayment {
+                        to: payment_to,
+                        from: fee_payer,
+                        fee: common.fee,
+                        amount: payment.amount,
+                        nonce: common.nonce,
+                        memo,
+                        valid_until: common.valid_until,
+                    }),
+                    payment.receiver_pk,
+                )
+            }
+            "Stake_delegation" => {
+                let (_, delegation): (String, SetDelegateJsonData) =
+                    serde_json::from_value(body.clone()).map_err(|e| e.to_string())?;
+                if delegation.delegator != common.fee_payer_pk {
+                    return Err("delegator is not the fee payer".into());
+                }
+                let new_delegate = parse_compressed_public_key(&delegation.new_delegate)?;
+                (
+                    UserCommandPayload::StakeDelegation(MinaStakeDelegation {
+                        to: new_delegate,
+                        from: fee_payer,
+                        fee: common.fee,
+                        nonce: common.nonce,
+                        memo,
+                        valid_until: common.valid_until,
+                    }),
+                    delegation.new_delegate,
+                )
+            }
+            _ => return Err(format!("unknown command body {tag}")),
+        };
+        let signature = SignatureData::from_base58(&command.signature)?;
+        Ok(Self {
+            payload,
+            from: common.fee_payer_pk.clone(),
+            to,
+            signer: parse_public_key(&command.signer)?,
+            signature: signature_from_decimal(&signature.field, &signature.scalar)?,
+        })
+    }
+}
+
+#[cfg(feature = "audit")]
+impl DecodedSignedCommand {
+    pub fn kind(&self) -> &'static str {
+        match self.payload {
+            UserCommandPayload::Payment(_) => "payment",
+            UserCommandPayload::StakeDelegation(_) => "stakeDelegation",
+        }
+    }
+
+    pub fn nonce(&self) -> u32 {
+        match &self.payload {
+            UserCommandPayload::Payment(p) => p.nonce,
+            UserCommandPayload::StakeDelegation(d) => d.nonce,
+        }
+    }
+
+    pub fn valid_until(&self) -> u32 {
+        match &self.payload {
+            UserCommandPayload::Payment(p) => p.valid_until,
+            UserCommandPayload::StakeDelegation(d) => d.valid_until,
+        }
+    }
+}
+
+/// Only the default token can be represented by [MinaPayment] and [MinaStakeDelegation],
+/// token ids are decimal strings or numbers
+fn check_default_token(token: Option<&Value>) -> Result<(), String> {
+    match token {
+        None => Ok(()),
+        Some(Value::String(token)) if token == "1" => Ok(()),
+        Some(token) if token.as_u64() == Some(1) => Ok(()),
+        Some(token) => Err(format!("token {token} is not supported")),
+    }
+}
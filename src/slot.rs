@@ -0,0 +1,225 @@
+use crate::*;
+use js_sys::{Date, Object, Reflect};
+use mina_signer::NetworkId;
+
+#[wasm_bindgen(typescript_custom_section)]
+const SLOT_TIME: &'static str = r#"
+/** A `Date` or unix time in milliseconds */
+export type Timestamp = Date | number;
+
+/** Genesis timestamp and slot duration of a network */
+export interface SlotTime {
+    genesisTimestamp: Timestamp;
+    /** Milliseconds */
+    slotDuration: number;
+}
+
+/** Global slot number, or the time after which the transaction must not be included */
+export type ValidUntil = UInt32 | { expiresAt: Timestamp };
+
+export interface ClientOptions {
+    /** Overrides the genesis timestamp and slot duration of `network`, for custom networks */
+    slotTime?: SlotTime;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Timestamp")]
+    pub type Timestamp;
+
+    #[wasm_bindgen(typescript_type = r#""mainnet" | "testnet" | SlotTime"#)]
+    pub type SlotTimeLike;
+
+    #[wasm_bindgen(method, getter, js_name = slotTime)]
+    pub fn slot_time(this: &ClientOptions) -> JsValue;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotTimeData {
+    /// Unix time in milliseconds
+    pub genesis_timestamp: f64,
+    /// Milliseconds
+    pub slot_duration: f64,
+}
+
+impl SlotTimeData {
+    pub fn of_network(network_id: NetworkId) -> Self {
+        let genesis_timestamp = match network_id {
+            NetworkId::MAINNET => constants::MAINNET_GENESIS_TIMESTAMP_MS,
+            NetworkId::TESTNET => constants::TESTNET_GENESIS_TIMESTAMP_MS,
+        };
+        Self {
+            genesis_timestamp: genesis_timestamp as f64,
+            slot_duration: constants::SLOT_DURATION_MS as f64,
+        }
+    }
+
+    /// Reads `ClientOptions.slotTime`
+    pub(crate) fn from_options(options: &ClientOptions) -> Result<Option<Self>, JsError> {
+        let slot_time = options.slot_time();
+        if slot_time.is_undefined() || slot_time.is_null() {
+            Ok(None)
+        } else {
+            Self::from_js(&slot_time)
+                .map(Some)
+                .map_err(|e| JsError::new(&format!("Invalid slotTime: {e}")))
+        }
+    }
+
+    fn from_js(value: &JsValue) -> Result<Self, String> {
+        if let Some(network) = value.as_string() {
+            return match network.as_str() {
+                "mainnet" => Ok(Self::of_network(NetworkId::MAINNET)),
+                "testnet" => Ok(Self::of_network(NetworkId::TESTNET)),
+                _ => Err(format!(
+                    "unknown network '{network}', expected 'mainnet', 'testnet' or a SlotTime"
+                )),
+            };
+        }
+        let genesis_timestamp = timestamp_to_millis(&get(value, "genesisTimestamp")?)
+            .map_err(|e| format!("genesisTimestamp {e}"))?;
+        let slot_duration = get(value, "slotDuration")?
+            .as_f64()
+            .filter(|d| d.is_finite() && *d >= 1.)
+            .ok_or("slotDuration should be a positive number of milliseconds")?;
+        Ok(Self {
+            genesis_timestamp,
+            slot_duration,
+        })
+    }
+
+    /// The global slot `millis` falls in
+    pub fn slot_at(&self, millis: f64) -> Result<u32, String> {
+        let slot = ((millis - self.genesis_timestamp) / self.slot_duration).floor();
+        if slot < 0. {
+            Err("time is before the genesis timestamp".into())
+        } else if slot > u32::MAX as f64 {
+            Err("time is past the last global slot".into())
+        } else {
+            Ok(slot as u32)
+        }
+    }
+
+    /// Start of a global slot, unix time in milliseconds
+    pub fn slot_start(&self, slot: u32) -> f64 {
+        self.genesis_timestamp + slot as f64 * self.slot_duration
+    }
+
+    /// The last slot that ends by `millis`, blocks of later slots could be produced after it
+    pub fn expiry_slot(&self, millis: f64) -> Result<u32, String> {
+        self.slot_at(millis)?
+            .checked_sub(1)
+            .ok_or_else(|| "no slot ends by then".into())
+    }
+}
+
+impl ClientImpl {
+    /// The slot of `validUntil: { expiresAt }`, `None` for anything else
+    #[cfg(any(feature = "payment", feature = "delegation"))]
+    pub(crate) fn expires_at_slot(&self, valid_until: &JsValue) -> Result<Option<u32>, JsError> {
+        if !valid_until.is_object() {
+            return Ok(None);
+        }
+        let expires_at = get(valid_until, "expiresAt").map_err(map_js_err)?;
+        timestamp_to_millis(&expires_at)
+            .and_then(|millis| self.slot_time.expiry_slot(millis))
+            .map(Some)
+            .map_err(|e| JsError::new(&format!("Invalid validUntil.expiresAt: {e}")))
+    }
+
+    /// Copy of a payment or stake delegation with `validUntil: { expiresAt }` replaced by its slot,
+    /// anything else is returned as is
+    #[cfg(any(feature = "payment", feature = "delegation"))]
+    pub(crate) fn resolve_valid_until(&self, transaction: &JsValue) -> Result<JsValue, JsError> {
+        if !transaction.is_object() {
+            return Ok(transaction.clone());
+        }
+        let valid_until = get(transaction, "validUntil").map_err(map_js_err)?;
+        let Some(slot) = self.expires_at_slot(&valid_until)? else {
+            return Ok(transaction.clone());
+        };
+        let resolved = Object::assign(&Object::new(), transaction.unchecked_ref());
+        Reflect::set(&resolved, &"validUntil".into(), &slot.into())
+            .map_err(|_| JsError::new("Failed to set validUntil"))?;
+        Ok(resolved.into())
+    }
+
+    /// Same as [Self::resolve_valid_until], also resolves the `data` of `Signed<T>`
+    #[cfg(any(
+        feature = "describe",
+        feature = "receipt",
+        feature = "ledger",
+        feature = "rosetta"
+    ))]
+    pub(crate) fn resolve_transaction_valid_until(
+        &self,
+        transaction: &JsValue,
+    ) -> Result<JsValue, JsError> {
+        let has = |key: &str| Reflect::has(transaction, &key.into()).unwrap_or(false);
+        if !(transaction.is_object() && has("signature") && has("data")) {
+            return self.resolve_valid_until(transaction);
+        }
+        let data = get(transaction, "data").map_err(map_js_err)?;
+        let resolved_data = self.resolve_valid_until(&data)?;
+        if resolved_data == data {
+            return Ok(transaction.clone());
+        }
+        let resolved = Object::assign(&Object::new(), transaction.unchecked_ref());
+        Reflect::set(&resolved, &"data".into(), &resolved_data)
+            .map_err(|_| JsError::new("Failed to set data"))?;
+        Ok(resolved.into())
+    }
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// The global slot `time` falls in on the network of this client
+    #[wasm_bindgen(js_name = globalSlotAt)]
+    pub fn global_slot_at(&self, time: Timestamp) -> Result<u32, JsError> {
+        let millis = timestamp_to_millis(&time).map_err(|e| JsError::new(&format!("time {e}")))?;
+        self.client().slot_time.slot_at(millis).map_err(map_js_err)
+    }
+
+    /// Start of a global slot on the network of this client
+    #[wasm_bindgen(js_name = globalSlotStart)]
+    pub fn global_slot_start(&self, slot: u32) -> Date {
+        Date::new(&self.client().slot_time.slot_start(slot).into())
+    }
+}
+
+/// The global slot `time` falls in, `slotTime` is a network name or custom values
+#[wasm_bindgen(js_name = timeToGlobalSlot)]
+pub fn time_to_global_slot(time: Timestamp, slot_time: SlotTimeLike) -> Result<u32, JsError> {
+    let slot_time = SlotTimeData::from_js(&slot_time).map_err(map_js_err)?;
+    let millis = timestamp_to_millis(&time).map_err(|e| JsError::new(&format!("time {e}")))?;
+    slot_time.slot_at(millis).map_err(map_js_err)
+}
+
+/// Start of a global slot, `slotTime` is a network name or custom values
+#[wasm_bindgen(js_name = globalSlotToTime)]
+pub fn global_slot_to_time(slot: u32, slot_time: SlotTimeLike) -> Result<Date, JsError> {
+    let slot_time = SlotTimeData::from_js(&slot_time).map_err(map_js_err)?;
+    Ok(Date::new(&slot_time.slot_start(slot).into()))
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, String> {
+    if !target.is_object() {
+        return Err(format!("expected an object with {key}"));
+    }
+    Reflect::get(target, &key.into()).map_err(|_| format!("failed to read {key}"))
+}
+
+fn timestamp_to_millis(value: &JsValue) -> Result<f64, String> {
+    let millis = match value.dyn_ref::<Date>() {
+        Some(date) => date.get_time(),
+        None => value
+            .as_f64()
+            .ok_or("should be a Date or unix time in milliseconds")?,
+    };
+    if millis.is_finite() {
+        Ok(millis)
+    } else {
+        Err("is not a valid time".into())
+    }
+}
@@ -1,8 +1,11 @@
 use crate::*;
 use mina_hasher::{Hashable, ROInput};
+#[cfg(feature = "serialization")]
 use mina_serialization_types::{common::*, json::*};
 use mina_signer::{CompressedPubKey, NetworkId};
+#[cfg(feature = "serialization")]
 use num_traits::identities::One;
+use serde::{Deserialize, Serialize};
 
 #[wasm_bindgen(typescript_custom_section)]
 const STAKE_DELEGATION: &'static str = r#"
@@ -12,7 +15,7 @@ export interface StakeDelegation {
     fee: UInt64;
     nonce: UInt32;
     memo?: string;
-    validUntil?: UInt32;
+    validUntil?: ValidUntil;
 }
 "#;
 
@@ -21,111 +24,71 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "StakeDelegation")]
     pub type StakeDelegation;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn to(this: &StakeDelegation) -> String;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn from(this: &StakeDelegation) -> String;
-
-    // u64 can be either f64 or bigint in js,
-    // use JsValue to handle the conversion
-    #[wasm_bindgen(method, getter)]
-    pub fn fee(this: &StakeDelegation) -> JsValue;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn nonce(this: &StakeDelegation) -> JsValue;
-
-    #[wasm_bindgen(method, getter)]
-    pub fn memo(this: &StakeDelegation) -> Option<String>;
-
-    #[wasm_bindgen(method, getter, js_name = validUntil)]
-    pub fn valid_until(this: &StakeDelegation) -> JsValue;
-
     #[wasm_bindgen(typescript_type = "Signed<StakeDelegation>")]
     pub type SignedStakeDelegation;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn signature(this: &SignedStakeDelegation) -> Signature;
+    #[wasm_bindgen(typescript_type = "Signed<StakeDelegation, SignatureLike>")]
+    pub type VerifiableStakeDelegation;
+}
 
-    #[wasm_bindgen(method, getter)]
-    pub fn data(this: &SignedStakeDelegation) -> StakeDelegation;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StakeDelegationData {
+    pub to: String,
+    pub from: String,
+    // u64 can be either f64, bigint or string in js
+    #[serde(with = "uint")]
+    pub fee: u64,
+    #[serde(with = "uint")]
+    pub nonce: u32,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default, with = "option_uint")]
+    pub valid_until: Option<u32>,
 }
 
 impl StakeDelegation {
-    pub fn fee_u64(&self) -> Result<u64, JsError> {
-        js_to_string(&self.fee()).parse().map_err(map_js_err)
+    pub fn to_data(&self) -> Result<StakeDelegationData, JsError> {
+        from_js(self)
     }
 
-    pub fn nonce_u32(&self) -> Result<u32, JsError> {
-        js_to_string(&self.nonce()).parse().map_err(map_js_err)
+    pub fn try_to_mina_stake_delegation(&self) -> Result<MinaStakeDelegation, JsError> {
+        (&self.to_data()?).try_into()
     }
+}
 
-    pub fn valid_until_u32(&self) -> Result<Option<u32>, JsError> {
-        let valid_until = self.valid_until();
-        if valid_until.is_null() || valid_until.is_undefined() {
-            Ok(None)
-        } else {
-            let s = js_to_string(&valid_until);
-            if s.is_empty() {
-                Ok(None)
-            } else {
-                Ok(Some(s.parse().map_err(map_js_err)?))
-            }
-        }
+impl SignedStakeDelegation {
+    pub fn to_data(&self) -> Result<SignedData<StakeDelegationData>, JsError> {
+        from_js(self)
     }
+}
 
-    pub fn try_to_mina_stake_delegation(&self) -> Result<MinaStakeDelegation, JsError> {
+impl TryFrom<&StakeDelegationData> for MinaStakeDelegation {
+    type Error = JsError;
+
+    fn try_from(v: &StakeDelegationData) -> Result<Self, Self::Error> {
         Ok(MinaStakeDelegation {
-            to: CompressedPubKey::from_address(self.to().as_str()).map_err(map_js_err)?,
-            from: CompressedPubKey::from_address(self.from().as_str()).map_err(map_js_err)?,
-            fee: self.fee_u64()?,
-            nonce: self.nonce_u32()?,
-            memo: string_to_memo(self.memo()),
-            valid_until: match self.valid_until_u32()? {
-                None => u32::max_value(),
-                Some(i) => i,
-            },
+            to: parse_compressed_public_key(v.to.as_str()).map_err(map_js_err)?,
+            from: parse_compressed_public_key(v.from.as_str()).map_err(map_js_err)?,
+            fee: v.fee,
+            nonce: v.nonce,
+            memo: string_to_memo(v.memo.clone()),
+            valid_until: v.valid_until.unwrap_or(u32::MAX),
         })
     }
 }
 
-#[wasm_bindgen(inline_js = r#"
-        function new_stake_delegation(to, from, fee, nonce, memo, validUntil) {
-            return {
-                to,
-                from,
-                fee,
-                nonce,
-                memo,
-                validUntil,
-            }
-        }
-
-        function new_signed_stake_delegation(signature, data) {
-            return {
-                signature,
-                data,
-            }
-        }
-
-        module.exports = {
-            new_stake_delegation, new_signed_stake_delegation
+impl From<&MinaStakeDelegation> for StakeDelegationData {
+    fn from(v: &MinaStakeDelegation) -> Self {
+        Self {
+            to: v.to.into_address(),
+            from: v.from.into_address(),
+            fee: v.fee,
+            nonce: v.nonce,
+            memo: memo_to_string(&v.memo),
+            valid_until: Some(v.valid_until),
         }
-    "#)]
-extern "C" {
-    pub fn new_stake_delegation(
-        to: String,
-        from: String,
-        fee: u64,
-        nonce: u32,
-        memo: Option<String>,
-        valid_until: Option<u32>,
-    ) -> StakeDelegation;
-
-    pub fn new_signed_stake_delegation(
-        signature: Signature,
-        data: StakeDelegation,
-    ) -> SignedStakeDelegation;
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -140,15 +103,10 @@ pub struct MinaStakeDelegation {
 
 impl From<MinaStakeDelegation> for StakeDelegation {
     fn from(v: MinaStakeDelegation) -> Self {
-        let memo = memo_to_string(&v.memo);
-        new_stake_delegation(
-            v.to.into_address(),
-            v.from.into_address(),
-            v.fee,
-            v.nonce,
-            memo,
-            Some(v.valid_until),
-        )
+        StakeDelegationData::from(&v)
+            .serialize(&JS_SERIALIZER)
+            .expect("StakeDelegationData is always serializable")
+            .unchecked_into()
     }
 }
 
@@ -160,6 +118,7 @@ impl TryFrom<StakeDelegation> for MinaStakeDelegation {
     }
 }
 
+#[cfg(feature = "serialization")]
 impl From<MinaStakeDelegation> for SignedCommandJson {
     fn from(p: MinaStakeDelegation) -> Self {
         let dummy_signature = MinaSignature {
@@ -189,22 +148,42 @@ impl From<MinaStakeDelegation> for SignedCommandJson {
     }
 }
 
-impl TryFrom<StakeDelegation> for SignedCommandJson {
+#[cfg(feature = "serialization")]
+impl TryFrom<&StakeDelegationData> for SignedCommandJson {
     type Error = JsError;
 
-    fn try_from(v: StakeDelegation) -> Result<Self, Self::Error> {
+    fn try_from(v: &StakeDelegationData) -> Result<Self, Self::Error> {
         let p: MinaStakeDelegation = v.try_into()?;
         Ok(p.into())
     }
 }
 
+#[cfg(feature = "serialization")]
+impl TryFrom<StakeDelegation> for SignedCommandJson {
+    type Error = JsError;
+
+    fn try_from(v: StakeDelegation) -> Result<Self, Self::Error> {
+        (&v.to_data()?).try_into()
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl TryFrom<&SignedData<StakeDelegationData>> for SignedCommandJson {
+    type Error = JsError;
+
+    fn try_from(v: &SignedData<StakeDelegationData>) -> Result<Self, Self::Error> {
+        let mut result: Self = (&v.data).try_into()?;
+        result.signature = signature_to_json((&v.signature).try_into()?);
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "serialization")]
 impl TryFrom<SignedStakeDelegation> for SignedCommandJson {
     type Error = JsError;
 
     fn try_from(v: SignedStakeDelegation) -> Result<Self, Self::Error> {
-        let mut result: Self = v.data().try_into()?;
-        result.signature = signature_to_json(v.signature().try_into()?);
-        Ok(result)
+        (&v.to_data()?).try_into()
     }
 }
 
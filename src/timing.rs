@@ -0,0 +1,101 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ACCOUNT_TIMING: &'static str = r#"
+/** Vesting schedule of a timed account, amounts in nanomina */
+export interface AccountTiming {
+    initialMinimumBalance: UInt64;
+    /** Global slot */
+    cliffTime: UInt32;
+    cliffAmount: UInt64;
+    /** Slots */
+    vestingPeriod: UInt32;
+    vestingIncrement: UInt64;
+}
+
+export interface TimedBalance {
+    /** Nanomina that cannot be spent at the slot */
+    locked: bigint;
+    /** Nanomina of the initial minimum balance that vested by the slot */
+    vested: bigint;
+    /** Balance minus the locked nanomina, set when a balance is given */
+    spendable?: bigint;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "AccountTiming")]
+    pub type AccountTiming;
+
+    #[wasm_bindgen(typescript_type = "TimedBalance")]
+    pub type TimedBalance;
+}
+
+/// Vesting schedule of a timed account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTimingData {
+    #[serde(with = "uint")]
+    pub initial_minimum_balance: u64,
+    #[serde(with = "uint")]
+    pub cliff_time: u32,
+    #[serde(with = "uint")]
+    pub cliff_amount: u64,
+    #[serde(with = "uint")]
+    pub vesting_period: u32,
+    #[serde(with = "uint")]
+    pub vesting_increment: u64,
+}
+
+impl AccountTimingData {
+    /// The balance that cannot be spent at `global_slot`, `Account.min_balance_at_slot` of the daemon
+    pub fn locked_at(&self, global_slot: u32) -> u64 {
+        if global_slot < self.cliff_time {
+            self.initial_minimum_balance
+        } else if self.vesting_period == 0 {
+            // Fully vested at the cliff
+            0
+        } else {
+            let past_cliff = self
+                .initial_minimum_balance
+                .saturating_sub(self.cliff_amount);
+            let periods = ((global_slot - self.cliff_time) / self.vesting_period) as u64;
+            past_cliff.saturating_sub(periods.saturating_mul(self.vesting_increment))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimedBalanceData {
+    pub locked: u64,
+    pub vested: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<u64>,
+}
+
+impl TimedBalanceData {
+    pub fn new(timing: &AccountTimingData, global_slot: u32, balance: Option<u64>) -> Self {
+        let locked = timing.locked_at(global_slot);
+        Self {
+            locked,
+            vested: timing.initial_minimum_balance - locked,
+            spendable: balance.map(|balance| balance.saturating_sub(locked)),
+        }
+    }
+}
+
+/// The locked and vested part of a timed account at a global slot,
+/// and how much of `balance` can be spent
+#[wasm_bindgen(js_name = timedBalanceAt)]
+pub fn timed_balance_at(
+    timing: AccountTiming,
+    global_slot: u32,
+    balance: Option<UInt64>,
+) -> Result<TimedBalance, JsError> {
+    let timing: AccountTimingData = from_js(&timing)?;
+    let balance = balance.map(|b| b.to_nanomina()).transpose()?;
+    let timed = TimedBalanceData::new(&timing, global_slot, balance);
+    Ok(to_js(&timed)?.unchecked_into())
+}
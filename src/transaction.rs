@@ -0,0 +1,130 @@
+use crate::*;
+use js_sys::{Object, Reflect};
+use serde::{de::IgnoredAny, Deserialize, Deserializer};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TRANSACTION: &'static str = r#"
+export type Transaction = Payment | StakeDelegation | Signed<Payment> | Signed<StakeDelegation>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Transaction")]
+    pub type Transaction;
+
+    #[wasm_bindgen(typescript_type = "Transaction[]")]
+    pub type TransactionArray;
+}
+
+/// A payment or a stake delegation, the signature of signed ones is not checked
+#[derive(Debug, Clone)]
+pub(crate) enum TransactionData {
+    Signed(UnsignedTransactionData),
+    Unsigned(UnsignedTransactionData),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SignedTransactionData {
+    #[serde(rename = "signature")]
+    _signature: IgnoredAny,
+    data: UnsignedTransactionData,
+}
+
+/// A payment when it has an `amount`, a stake delegation otherwise
+#[derive(Debug, Clone)]
+pub(crate) enum UnsignedTransactionData {
+    Payment(PaymentData),
+    StakeDelegation(StakeDelegationData),
+}
+
+/// The fields of both, unknown fields are rejected so that a mistyped `amount`
+/// is not read as a stake delegation
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct UnsignedTransactionFields {
+    to: String,
+    from: String,
+    #[serde(with = "uint")]
+    fee: u64,
+    #[serde(default, with = "option_uint")]
+    amount: Option<u64>,
+    #[serde(with = "uint")]
+    nonce: u32,
+    #[serde(default)]
+    memo: Option<String>,
+    #[serde(default, with = "option_uint")]
+    valid_until: Option<u32>,
+}
+
+impl<'de> Deserialize<'de> for UnsignedTransactionData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = UnsignedTransactionFields::deserialize(deserializer)?;
+        Ok(match fields.amount {
+            Some(amount) => Self::Payment(PaymentData {
+                to: fields.to,
+                from: fields.from,
+                fee: fields.fee,
+                amount,
+                nonce: fields.nonce,
+                memo: fields.memo,
+                valid_until: fields.valid_until,
+            }),
+            None => Self::StakeDelegation(StakeDelegationData {
+                to: fields.to,
+                from: fields.from,
+                fee: fields.fee,
+                nonce: fields.nonce,
+                memo: fields.memo,
+                valid_until: fields.valid_until,
+            }),
+        })
+    }
+}
+
+const TRANSACTION_FIELDS: [&str; 7] =
+    ["to", "from", "fee", "amount", "nonce", "memo", "validUntil"];
+
+/// serde-wasm-bindgen only reads the fields a struct declares, so `deny_unknown_fields`
+/// does not see the other keys of js objects
+fn check_unknown_fields(transaction: &JsValue, path: &str) -> Result<(), JsError> {
+    let Some(object) = transaction.dyn_ref::<Object>() else {
+        return Ok(());
+    };
+    for key in Object::keys(object)
+        .iter()
+        .filter_map(|key| key.as_string())
+    {
+        if !TRANSACTION_FIELDS.contains(&key.as_str()) {
+            return Err(JsError::new(&format!(
+                "Invalid transaction: unknown field `{path}{key}`, expected one of {}",
+                TRANSACTION_FIELDS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl TransactionData {
+    /// `Signed<T>` when it has both a `signature` and `data`
+    pub fn parse(transaction: &JsValue) -> Result<Self, JsError> {
+        let has = |key: &str| Reflect::has(transaction, &key.into()).unwrap_or(false);
+        if has("signature") && has("data") {
+            let data = Reflect::get(transaction, &"data".into()).unwrap_or_default();
+            check_unknown_fields(&data, "data.")?;
+            from_js::<SignedTransactionData>(transaction).map(|t| Self::Signed(t.data))
+        } else {
+            check_unknown_fields(transaction, "")?;
+            from_js(transaction).map(Self::Unsigned)
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, Self::Signed(_))
+    }
+
+    pub fn into_unsigned(self) -> UnsignedTransactionData {
+        match self {
+            Self::Signed(data) | Self::Unsigned(data) => data,
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use crate::*;
+use ark_ff::{BigInteger256, PrimeField};
+#[cfg(feature = "serialization")]
 use mina_serialization_types::{json::*, signatures::*};
+#[cfg(feature = "serialization")]
 use mina_signer::CompressedPubKey;
+use num_bigint::BigUint;
+use num_traits::Num;
+use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Display;
 use wasm_bindgen::JsError;
 
@@ -8,6 +14,7 @@ use wasm_bindgen::JsError;
 const UNITS: &'static str = r#"
 export type UInt32 = number | bigint | string;
 
+/** Nanomina when used as an amount or fee, which can also be given in MINA, e.g. "1.5 MINA" */
 export type UInt64 = number | bigint | string;
 "#;
 
@@ -15,6 +22,281 @@ pub(crate) fn map_js_err<T: Display>(err: T) -> JsError {
     JsError::new(&format!("{err}"))
 }
 
+/// Serializes u64 values into bigint, which is what wasm-bindgen does for u64
+pub(crate) const JS_SERIALIZER: serde_wasm_bindgen::Serializer =
+    serde_wasm_bindgen::Serializer::new().serialize_large_number_types_as_bigints(true);
+
+/// Prefixes the error with the path of the offending field, e.g. `Invalid data.fee: ...`
+fn path_err_to_string<E: Display>(err: serde_path_to_error::Error<E>) -> String {
+    match err.path().to_string().as_str() {
+        "." => err.inner().to_string(),
+        path => format!("Invalid {path}: {}", err.inner()),
+    }
+}
+
+/// Same as [from_js] but with a plain error message
+pub(crate) fn deserialize_js<T: DeserializeOwned>(value: &JsValue) -> Result<T, String> {
+    serde_path_to_error::deserialize(serde_wasm_bindgen::Deserializer::from(value.clone()))
+        .map_err(path_err_to_string)
+}
+
+/// Converts a js value into a rust value in a single pass
+pub(crate) fn from_js<T: DeserializeOwned>(value: &JsValue) -> Result<T, JsError> {
+    deserialize_js(value).map_err(map_js_err)
+}
+
+/// Parses json with field specific errors
+#[cfg(any(
+    feature = "rosetta",
+    feature = "policy",
+    feature = "ledger",
+    feature = "audit"
+))]
+pub(crate) fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, String> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(path_err_to_string)?;
+    deserializer.end().map_err(|e| e.to_string())?;
+    Ok(value)
+}
+
+/// Converts a rust value into a js value in a single pass
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsError> {
+    value.serialize(&JS_SERIALIZER).map_err(map_js_err)
+}
+
+/// (De)serializes unsigned integers that are represented as
+/// number, bigint or decimal string in js, u64 amounts also accept MINA strings like `1.5 MINA`
+pub(crate) mod uint {
+    use serde::{
+        de::{self, Visitor},
+        Deserializer, Serialize, Serializer,
+    };
+    use std::{fmt, marker::PhantomData};
+
+    /// `Number.MAX_SAFE_INTEGER`, js numbers above it may already have lost precision
+    const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+    pub(super) struct UIntVisitor<T>(pub(super) PhantomData<T>);
+
+    impl<T> UIntVisitor<T> {
+        fn bits() -> usize {
+            std::mem::size_of::<T>() * 8
+        }
+    }
+
+    impl<'de, T: TryFrom<u64>> Visitor<'de> for UIntVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "an unsigned integer of at most {} bits as number, bigint or decimal string",
+                Self::bits()
+            )
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            T::try_from(v)
+                .map_err(|_| E::custom(format!("{v} exceeds the u{} range", Self::bits())))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            match u64::try_from(v) {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::custom(format!("{v} is negative"))),
+            }
+        }
+
+        /// js numbers that are not safe integers end up here
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            if !v.is_finite() {
+                Err(E::custom(format!("{v} is not a finite number")))
+            } else if v < 0.0 {
+                Err(E::custom(format!("{v} is negative")))
+            } else if v.fract() != 0.0 {
+                Err(E::custom(format!("{v} is not an integer")))
+            } else if v > MAX_SAFE_INTEGER {
+                Err(E::custom(format!(
+                    "{v:e} is above Number.MAX_SAFE_INTEGER and may have lost precision, pass it as a bigint or a decimal string"
+                )))
+            } else {
+                self.visit_u64(v as u64)
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            if let Some(mina) = v.strip_suffix(" MINA") {
+                if std::mem::size_of::<T>() != 8 {
+                    return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+                }
+                return crate::parse_mina(mina.trim())
+                    .map_err(E::custom)
+                    .and_then(|v| self.visit_u64(v));
+            }
+            if v.starts_with('-') {
+                return Err(E::custom(format!("'{v}' is negative")));
+            }
+            if v.contains(['e', 'E']) {
+                return Err(E::custom(format!(
+                    "'{v}' is in exponent notation, pass it as a bigint or a plain decimal string"
+                )));
+            }
+            if v.contains('.') {
+                return Err(E::custom(format!("'{v}' is not an integer")));
+            }
+            match v.parse() {
+                Ok(v) => self.visit_u64(v),
+                Err(_) if !v.is_empty() && v.bytes().all(|b| b.is_ascii_digit()) => Err(E::custom(
+                    format!("'{v}' exceeds the u{} range", Self::bits()),
+                )),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<u64>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_any(UIntVisitor(PhantomData))
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(v: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        v.serialize(serializer)
+    }
+}
+
+/// Same as [uint] but `null`, `undefined` and empty string are treated as `None`
+#[cfg(any(feature = "payment", feature = "delegation"))]
+pub(crate) mod option_uint {
+    use super::uint::UIntVisitor;
+    use serde::{
+        de::{self, Visitor},
+        Deserializer, Serialize, Serializer,
+    };
+    use std::{fmt, marker::PhantomData};
+
+    struct OptionUIntVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: TryFrom<u64>> Visitor<'de> for OptionUIntVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an optional unsigned integer as number, bigint or decimal string")
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            UIntVisitor(PhantomData).visit_u64(v).map(Some)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            UIntVisitor(PhantomData).visit_i64(v).map(Some)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            UIntVisitor(PhantomData).visit_f64(v).map(Some)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            if v.is_empty() {
+                Ok(None)
+            } else {
+                UIntVisitor(PhantomData).visit_str(v).map(Some)
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<u64>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        deserializer.deserialize_any(OptionUIntVisitor(PhantomData))
+    }
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        v: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        v.serialize(serializer)
+    }
+}
+
+/// Amounts of genesis ledgers and precomputed blocks are in MINA, as decimal strings or numbers
+#[cfg(any(feature = "ledger", feature = "rosetta", feature = "audit"))]
+pub(crate) mod mina_amount {
+    use serde::{
+        de::{self, Visitor},
+        Deserializer,
+    };
+    use std::fmt;
+
+    struct MinaAmountVisitor;
+
+    impl<'de> Visitor<'de> for MinaAmountVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a MINA amount as decimal string or number")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            crate::parse_mina(v.trim()).map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            self.visit_str(&v.to_string())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_any(MinaAmountVisitor)
+    }
+}
+
+/// Parses a decimal or `0x` prefixed hex field element, rejecting values that are not below the modulus
+pub(crate) fn prime_field_from_str<F: PrimeField<BigInt = BigInteger256>>(
+    name: &str,
+    value: &str,
+) -> Result<F, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => BigUint::from_str_radix(hex, 16),
+        None => BigUint::from_str_radix(value, 10),
+    };
+    let repr: BigInteger256 = parsed
+        .map_err(|e| format!("Invalid {name}: {e}"))?
+        .try_into()
+        .map_err(|e| format!("Invalid {name}: {e}"))?;
+    F::from_repr(repr).ok_or_else(|| format!("Invalid {name}: {value} exceeds the modulus"))
+}
+
+/// Formats a field element as decimal, or as 64 digit `0x` prefixed hex
+pub(crate) fn prime_field_to_string<F: PrimeField<BigInt = BigInteger256>>(
+    value: &F,
+    hex: bool,
+) -> String {
+    let value: BigUint = value.into_repr().into();
+    if hex {
+        format!("0x{:0>64}", value.to_str_radix(16))
+    } else {
+        value.to_str_radix(10)
+    }
+}
+
+#[cfg(feature = "serialization")]
 pub(crate) fn compressed_pubkey_to_json(v: CompressedPubKey) -> PublicKeyJson {
     let ccp = CompressedCurvePoint {
         // This unwrap of a slice conversion is safe as a CompressedPubKey always has 32 bytes of data which the exact length of
@@ -29,6 +311,7 @@ pub(crate) fn compressed_pubkey_to_json(v: CompressedPubKey) -> PublicKeyJson {
     ccp.into()
 }
 
+#[cfg(feature = "serialization")]
 pub(crate) fn signature_to_json(t: MinaSignature) -> SignatureJson {
     let v1 = SignatureV1(
         (
@@ -48,12 +331,13 @@ pub(crate) fn signature_to_json(t: MinaSignature) -> SignatureJson {
     v1.into()
 }
 
+#[cfg(any(feature = "payment", feature = "delegation"))]
 pub(crate) fn string_to_memo(s: Option<String>) -> [u8; constants::MEMO_BYTES] {
     let mut memo = [0; constants::MEMO_BYTES];
     memo[0] = 1;
     if let Some(s) = s {
-        const MAX_BYTES: u8 = constants::MEMO_BYTES as u8 - 2;
-        memo[1] = MAX_BYTES.min(s.len() as u8);
+        // Clamped before the cast, 256 bytes would otherwise wrap to a length of 0
+        memo[1] = s.len().min(constants::MEMO_BYTES - 2) as u8;
         for (i, &b) in s
             .as_bytes()
             .iter()
@@ -66,24 +350,14 @@ pub(crate) fn string_to_memo(s: Option<String>) -> [u8; constants::MEMO_BYTES] {
     memo
 }
 
+#[cfg(any(feature = "payment", feature = "delegation"))]
+/// Memos decoded from the chain may have any length byte and bytes that are not utf-8,
+/// the length is clamped to the memo and invalid sequences are replaced
 pub(crate) fn memo_to_string(memo: &[u8; constants::MEMO_BYTES]) -> Option<String> {
-    let memo_len = memo[1] as usize;
+    let memo_len = (memo[1] as usize).min(constants::MEMO_BYTES - 2);
     if memo_len == 0 {
         None
     } else {
-        Some(unsafe { String::from_utf8_unchecked(memo[2..(memo_len + 2)].into()) })
+        Some(String::from_utf8_lossy(&memo[2..(memo_len + 2)]).into_owned())
     }
 }
-
-#[wasm_bindgen(inline_js = r#"
-        function js_to_string(v) {
-            return `${v}`
-        }
-
-        module.exports = {
-            js_to_string
-        }
-    "#)]
-extern "C" {
-    pub(crate) fn js_to_string(v: &JsValue) -> String;
-}
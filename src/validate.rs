@@ -0,0 +1,374 @@
+use crate::*;
+use mina_signer::CompressedPubKey;
+use serde::{Deserialize, Serialize};
+
+#[wasm_bindgen(typescript_custom_section)]
+const VALIDATION: &'static str = r#"
+export type ViolationCode =
+    | "invalidPublicKey"
+    | "feeTooLow"
+    | "zeroAmount"
+    | "amountOverflow"
+    | "expired"
+    | "invalidMemo"
+    | "memoTooLong"
+    | "insufficientBalance"
+    | "lockedBalance";
+
+export interface Violation {
+    code: ViolationCode;
+    /** The offending field, e.g. "fee" */
+    field: string;
+    message: string;
+}
+
+/** State of the sender account, enables the balance checks */
+export interface SenderAccount {
+    /** Nanomina */
+    balance: UInt64;
+    timing?: AccountTiming;
+    /**
+     * Slot the vesting schedule and `validUntil` are checked at,
+     * defaults to the current slot of the client network
+     */
+    globalSlot?: UInt32;
+}
+
+export interface ClientOptions {
+    /** Reject payments and stake delegations the daemon would not accept instead of signing them */
+    strict?: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Violation[]")]
+    pub type ViolationArray;
+
+    #[wasm_bindgen(typescript_type = "SenderAccount")]
+    pub type SenderAccount;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn strict(this: &ClientOptions) -> Option<bool>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub code: &'static str,
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl Violation {
+    fn new(code: &'static str, field: &'static str, message: String) -> Self {
+        Self {
+            code,
+            field,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderAccountData {
+    #[serde(with = "uint")]
+    pub balance: u64,
+    #[serde(default)]
+    pub timing: Option<AccountTimingData>,
+    #[serde(default, with = "option_uint")]
+    pub global_slot: Option<u32>,
+}
+
+/// Joins violations into a single error message
+pub(crate) fn violations_to_string(kind: &str, violations: &[Violation]) -> String {
+    let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+    format!("Invalid {kind}: {}", messages.join(", "))
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Checks a payment against the rules the daemon applies to user commands,
+    /// and against the balance and vesting schedule of `sender` when it is given,
+    /// an empty list means it is well formed
+    #[wasm_bindgen(js_name = validatePayment)]
+    pub fn validate_payment(
+        &self,
+        payment: Payment,
+        sender: Option<SenderAccount>,
+    ) -> Result<ViolationArray, JsError> {
+        let payment: Payment = self
+            .client()
+            .resolve_valid_until(&payment)?
+            .unchecked_into();
+        let payment = payment.to_data()?;
+        let sender: Option<SenderAccountData> = sender.map(|s| from_js(&s)).transpose()?;
+        let global_slot = self.client().validation_slot(sender.as_ref())?;
+        let mut violations = payment.violations(Some(global_slot));
+        if let Some(sender) = sender {
+            violations.extend(self.client().sender_violations(
+                &sender,
+                global_slot,
+                "amount",
+                payment.amount.checked_add(payment.fee),
+            )?);
+        }
+        Ok(to_js(&violations)?.unchecked_into())
+    }
+
+    /// Checks a stake delegation against the rules the daemon applies to user commands,
+    /// and against the balance and vesting schedule of `sender` when it is given,
+    /// an empty list means it is well formed
+    #[wasm_bindgen(js_name = validateStakeDelegation)]
+    pub fn validate_stake_delegation(
+        &self,
+        stake_delegation: StakeDelegation,
+        sender: Option<SenderAccount>,
+    ) -> Result<ViolationArray, JsError> {
+        let stake_delegation: StakeDelegation = self
+            .client()
+            .resolve_valid_until(&stake_delegation)?
+            .unchecked_into();
+        let stake_delegation = stake_delegation.to_data()?;
+        let sender: Option<SenderAccountData> = sender.map(|s| from_js(&s)).transpose()?;
+        let global_slot = self.client().validation_slot(sender.as_ref())?;
+        let mut violations = stake_delegation.violations(Some(global_slot));
+        if let Some(sender) = sender {
+            violations.extend(self.client().sender_violations(
+                &sender,
+                global_slot,
+                "fee",
+                Some(stake_delegation.fee),
+            )?);
+        }
+        Ok(to_js(&violations)?.unchecked_into())
+    }
+}
+
+impl ClientImpl {
+    /// `SenderAccount.globalSlot`, or the current slot of the client network
+    fn validation_slot(&self, sender: Option<&SenderAccountData>) -> Result<u32, JsError> {
+        match sender.and_then(|sender| sender.global_slot) {
+            Some(slot) => Ok(slot),
+            None => self
+                .slot_time
+                .slot_at(js_sys::Date::now())
+                .map_err(map_js_err),
+        }
+    }
+
+    /// Whether `sender` can spend `total`, the amount plus the fee, unset when it overflows,
+    /// at `global_slot`, violations are reported on `field`
+    pub fn sender_violations(
+        &self,
+        sender: &SenderAccountData,
+        global_slot: u32,
+        field: &'static str,
+        total: Option<u64>,
+    ) -> Result<Vec<Violation>, JsError> {
+        // An overflow is already reported as amountOverflow
+        let total = match total {
+            Some(total) => total,
+            None => return Ok(vec![]),
+        };
+        let mut violations = vec![];
+        if total > sender.balance {
+            violations.push(Violation::new(
+                "insufficientBalance",
+                field,
+                format!(
+                    "amount and fee of {total} exceed the balance of {} nanomina",
+                    sender.balance
+                ),
+            ));
+        } else if let Some(timing) = &sender.timing {
+            let timed = TimedBalanceData::new(timing, global_slot, Some(sender.balance));
+            if sender.balance - total < timed.locked {
+                violations.push(Violation::new(
+                    "lockedBalance",
+                    field,
+                    format!(
+                        "{} nanomina are locked at slot {global_slot}, at most {} nanomina can be spent",
+                        timed.locked,
+                        timed.spendable.unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+        Ok(violations)
+    }
+}
+
+impl MinaPayment {
+    /// `validUntil` is only checked when the current `global_slot` is known
+    pub fn violations(&self, global_slot: Option<u32>) -> Vec<Violation> {
+        let mut violations = vec![];
+        check_public_key(&mut violations, "from", &self.from);
+        check_public_key(&mut violations, "to", &self.to);
+        check_common(
+            &mut violations,
+            self.fee,
+            self.valid_until,
+            global_slot,
+            &self.memo,
+        );
+        if self.amount == 0 {
+            violations.push(Violation::new(
+                "zeroAmount",
+                "amount",
+                "amount should not be zero".into(),
+            ));
+        }
+        if self.amount.checked_add(self.fee).is_none() {
+            violations.push(Violation::new(
+                "amountOverflow",
+                "amount",
+                format!(
+                    "amount {} plus fee {} exceeds the u64 range",
+                    self.amount, self.fee
+                ),
+            ));
+        }
+        violations
+    }
+}
+
+impl MinaStakeDelegation {
+    /// Same as [MinaPayment::violations]
+    pub fn violations(&self, global_slot: Option<u32>) -> Vec<Violation> {
+        let mut violations = vec![];
+        check_public_key(&mut violations, "from", &self.from);
+        check_public_key(&mut violations, "to", &self.to);
+        check_common(
+            &mut violations,
+            self.fee,
+            self.valid_until,
+            global_slot,
+            &self.memo,
+        );
+        violations
+    }
+}
+
+impl PaymentData {
+    /// Same as [MinaPayment::violations], plus the checks that only apply to the js representation
+    pub fn violations(&self, global_slot: Option<u32>) -> Vec<Violation> {
+        let mut violations = vec![];
+        let from = decode_public_key(&mut violations, "from", &self.from);
+        let to = decode_public_key(&mut violations, "to", &self.to);
+        check_memo_length(&mut violations, &self.memo);
+        if let (Some(from), Some(to)) = (from, to) {
+            let payment = MinaPayment {
+                to,
+                from,
+                fee: self.fee,
+                amount: self.amount,
+                nonce: self.nonce,
+                memo: string_to_memo(self.memo.clone()),
+                valid_until: self.valid_until.unwrap_or(u32::MAX),
+            };
+            violations.extend(payment.violations(global_slot));
+        }
+        violations
+    }
+}
+
+impl StakeDelegationData {
+    /// Same as [MinaStakeDelegation::violations], plus the checks that only apply to the js representation
+    pub fn violations(&self, global_slot: Option<u32>) -> Vec<Violation> {
+        let mut violations = vec![];
+        let from = decode_public_key(&mut violations, "from", &self.from);
+        let to = decode_public_key(&mut violations, "to", &self.to);
+        check_memo_length(&mut violations, &self.memo);
+        if let (Some(from), Some(to)) = (from, to) {
+            let stake_delegation = MinaStakeDelegation {
+                to,
+                from,
+                fee: self.fee,
+                nonce: self.nonce,
+                memo: string_to_memo(self.memo.clone()),
+                valid_until: self.valid_until.unwrap_or(u32::MAX),
+            };
+            violations.extend(stake_delegation.violations(global_slot));
+        }
+        violations
+    }
+}
+
+fn decode_public_key(
+    violations: &mut Vec<Violation>,
+    field: &'static str,
+    address: &str,
+) -> Option<CompressedPubKey> {
+    match parse_compressed_public_key(address) {
+        Ok(pk) => Some(pk),
+        Err(e) => {
+            violations.push(Violation::new(
+                "invalidPublicKey",
+                field,
+                format!("{field}: {e}"),
+            ));
+            None
+        }
+    }
+}
+
+/// The compressed form does not tell whether the point is on the curve
+fn check_public_key(violations: &mut Vec<Violation>, field: &'static str, pk: &CompressedPubKey) {
+    let address = pk.into_address();
+    if parse_public_key(address.as_str()).is_err() {
+        violations.push(Violation::new(
+            "invalidPublicKey",
+            field,
+            format!("{field} {address} is not a point on the curve"),
+        ));
+    }
+}
+
+fn check_memo_length(violations: &mut Vec<Violation>, memo: &Option<String>) {
+    const MAX_BYTES: usize = constants::MEMO_BYTES - 2;
+    if let Some(memo) = memo.as_ref().filter(|memo| memo.len() > MAX_BYTES) {
+        violations.push(Violation::new(
+            "memoTooLong",
+            "memo",
+            format!(
+                "memo is {} bytes, only the first {MAX_BYTES} would be signed",
+                memo.len()
+            ),
+        ));
+    }
+}
+
+fn check_common(
+    violations: &mut Vec<Violation>,
+    fee: u64,
+    valid_until: u32,
+    global_slot: Option<u32>,
+    memo: &[u8; constants::MEMO_BYTES],
+) {
+    if fee < constants::MINIMUM_USER_COMMAND_FEE {
+        violations.push(Violation::new(
+            "feeTooLow",
+            "fee",
+            format!(
+                "fee {fee} is below the minimum of {} nanomina",
+                constants::MINIMUM_USER_COMMAND_FEE
+            ),
+        ));
+    }
+    // Commands can still be included at their validUntil slot
+    if let Some(slot) = global_slot.filter(|slot| valid_until < *slot) {
+        violations.push(Violation::new(
+            "expired",
+            "validUntil",
+            format!("validUntil {valid_until} is before the global slot {slot}"),
+        ));
+    }
+    if memo[0] != 1 || memo[1] as usize > constants::MEMO_BYTES - 2 {
+        violations.push(Violation::new(
+            "invalidMemo",
+            "memo",
+            "memo is not a byte string of at most 32 bytes".into(),
+        ));
+    }
+}